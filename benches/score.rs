@@ -0,0 +1,73 @@
+use bench_rs::score::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const ROUNDS: usize = 5;
+const RECORDS_PER_ROUND: usize = 1_000_000;
+
+fn main() {
+    let mut score = Score::new();
+    score.add_point_table("login", 1);
+    score.add_point_table("post_comment", 2);
+    score.add_point_table("checkout", 5);
+
+    println!(
+        "recording {} points in {} rounds",
+        ROUNDS * RECORDS_PER_ROUND,
+        ROUNDS
+    );
+
+    let mut allocated = Vec::with_capacity(ROUNDS);
+    let started_at = Instant::now();
+
+    for round in 1..=ROUNDS {
+        for i in 0..RECORDS_PER_ROUND {
+            match i % 3 {
+                0 => score.record("login"),
+                1 => score.record("post_comment"),
+                _ => score.record("checkout"),
+            }
+        }
+
+        let bytes = ALLOCATED.load(Ordering::SeqCst);
+        allocated.push(bytes);
+        println!(
+            "records: {:>9} / allocated: {:>6} bytes / total: {}",
+            round * RECORDS_PER_ROUND,
+            bytes,
+            score.total()
+        );
+    }
+
+    println!(
+        "{} records in {:?}",
+        ROUNDS * RECORDS_PER_ROUND,
+        started_at.elapsed()
+    );
+
+    assert!(
+        allocated.windows(2).all(|bytes| bytes[0] == bytes[1]),
+        "memory grew while recording: {:?}",
+        allocated
+    );
+}
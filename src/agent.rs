@@ -1,13 +1,51 @@
+pub mod rate_limiter;
+pub mod stats;
+
+use crate::agent::rate_limiter::*;
+use crate::agent::stats::*;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 use surf;
 use url::Url;
 
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentMetrics {
+    pub requests: usize,
+    pub latency: Duration,
+    pub rate_limit_wait: Duration,
+}
+
+type EndpointKey = (String, String);
+
+const MAX_ENDPOINTS: usize = 100;
+pub const OTHER_ENDPOINT: &str = "other";
+
+fn default_endpoint_key(_method: &str, path: &str) -> String {
+    match path.split_once('?') {
+        Some((path, _)) => path.to_string(),
+        None => path.to_string(),
+    }
+}
+
 #[derive(Clone)]
 pub struct Agent {
     client: surf::Client,
     user_agent: String,
+    rate_limiter: Option<RateLimiter>,
+    global_rate_limiter: Option<RateLimiter>,
+    metrics: Arc<Mutex<AgentMetrics>>,
+    endpoint_stats: Arc<RwLock<HashMap<EndpointKey, Arc<Mutex<EndpointStats>>>>>,
+    endpoint_key: fn(&str, &str) -> String,
+    is_stats_paused: Arc<AtomicBool>,
+    created_at: Instant,
 }
 
 impl Agent {
+    #[allow(deprecated)]
     pub fn new(base_url: impl Into<String>) -> Agent {
         let mut client = surf::client();
         client.set_base_url(Url::parse(&base_url.into()).unwrap());
@@ -15,6 +53,13 @@ impl Agent {
         Agent {
             client: client,
             user_agent: String::from(""),
+            rate_limiter: None,
+            global_rate_limiter: None,
+            metrics: Arc::new(Mutex::new(AgentMetrics::default())),
+            endpoint_stats: Arc::new(RwLock::new(HashMap::new())),
+            endpoint_key: default_endpoint_key,
+            is_stats_paused: Arc::new(AtomicBool::new(false)),
+            created_at: Instant::now(),
         }
     }
 
@@ -22,14 +67,130 @@ impl Agent {
         self.user_agent = user_agent.into();
     }
 
-    pub async fn get(&self, path: impl Into<String>) -> Result<surf::Response, surf::Error> {
+    pub fn set_rate_limiter(&mut self, rate_limiter: RateLimiter) {
+        self.rate_limiter = Some(rate_limiter);
+    }
+
+    pub fn set_endpoint_key(&mut self, endpoint_key: fn(&str, &str) -> String) {
+        self.endpoint_key = endpoint_key;
+    }
+
+    pub(crate) fn set_global_rate_limiter(&mut self, rate_limiter: RateLimiter) {
+        self.global_rate_limiter = Some(rate_limiter);
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.created_at.elapsed()
+    }
+
+    pub fn metrics(&self) -> AgentMetrics {
+        self.metrics.lock().unwrap().clone()
+    }
+
+    pub fn endpoint_stats(&self) -> Vec<EndpointStats> {
+        let mut endpoint_stats: Vec<_> = self
+            .endpoint_stats
+            .read()
+            .unwrap()
+            .values()
+            .map(|stats| stats.lock().unwrap().clone())
+            .collect();
+        endpoint_stats.sort_by(|a, b| (&a.path, &a.method).cmp(&(&b.path, &b.method)));
+        endpoint_stats
+    }
+
+    pub(crate) fn set_stats_paused(&self, is_stats_paused: bool) {
+        self.is_stats_paused
+            .store(is_stats_paused, Ordering::SeqCst);
+    }
+
+    fn record_stats(
+        &self,
+        method: surf::http::Method,
+        path: String,
+        offset: Duration,
+        latency: Duration,
+        status: Option<u16>,
+    ) {
+        if self.is_stats_paused.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let method = method.to_string();
+        let key = (method.clone(), (self.endpoint_key)(&method, &path));
+
+        // the map is only written the first time an endpoint is seen
+        let stats = self.endpoint_stats.read().unwrap().get(&key).cloned();
+        let stats = match stats {
+            Some(stats) => stats,
+            None => {
+                let mut endpoint_stats = self.endpoint_stats.write().unwrap();
+                // endpoints past the cap share one entry per method
+                let key = if endpoint_stats.len() >= MAX_ENDPOINTS
+                    && !endpoint_stats.contains_key(&key)
+                {
+                    (method, OTHER_ENDPOINT.to_string())
+                } else {
+                    key
+                };
+                endpoint_stats
+                    .entry(key.clone())
+                    .or_insert_with(|| Arc::new(Mutex::new(EndpointStats::new(key.0, key.1))))
+                    .clone()
+            }
+        };
+        stats.lock().unwrap().record(offset, latency, status);
+    }
+
+    async fn send(
+        &self,
+        method: surf::http::Method,
+        path: String,
+        request: surf::RequestBuilder,
+    ) -> Result<surf::Response, surf::Error> {
+        let mut rate_limit_wait = Duration::from_secs(0);
+        if let Some(rate_limiter) = &self.global_rate_limiter {
+            rate_limit_wait += rate_limiter.acquire().await;
+        }
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limit_wait += rate_limiter.acquire().await;
+        }
+
+        let started_at = Instant::now();
+        let response = request.header("User-Agent", self.user_agent.clone()).await;
+        let latency = started_at.elapsed();
+
+        let mut metrics = self.metrics.lock().unwrap();
+        metrics.requests += 1;
+        metrics.latency += latency;
+        metrics.rate_limit_wait += rate_limit_wait;
+        drop(metrics);
+
+        self.record_stats(
+            method,
+            path,
+            started_at.duration_since(self.created_at),
+            latency,
+            response
+                .as_ref()
+                .ok()
+                .map(|response| response.status() as u16),
+        );
+
+        response
+    }
+
+    pub(crate) async fn probe(
+        &self,
+        path: impl Into<String>,
+    ) -> Result<surf::Response, surf::Error> {
         self.client
             .get(path.into())
             .header("User-Agent", self.user_agent.clone())
             .await
     }
 
-    pub async fn post(
+    pub(crate) async fn probe_post(
         &self,
         path: impl Into<String>,
         payload: impl Into<surf::Body>,
@@ -41,16 +202,38 @@ impl Agent {
             .await
     }
 
+    pub async fn get(&self, path: impl Into<String>) -> Result<surf::Response, surf::Error> {
+        let path = path.into();
+        self.send(surf::http::Method::Get, path.clone(), self.client.get(path))
+            .await
+    }
+
+    pub async fn post(
+        &self,
+        path: impl Into<String>,
+        payload: impl Into<surf::Body>,
+    ) -> Result<surf::Response, surf::Error> {
+        let path = path.into();
+        self.send(
+            surf::http::Method::Post,
+            path.clone(),
+            self.client.post(path).body(payload.into()),
+        )
+        .await
+    }
+
     pub async fn put(
         &self,
         path: impl Into<String>,
         payload: impl Into<surf::Body>,
     ) -> Result<surf::Response, surf::Error> {
-        self.client
-            .put(path.into())
-            .header("User-Agent", self.user_agent.clone())
-            .body(payload.into())
-            .await
+        let path = path.into();
+        self.send(
+            surf::http::Method::Put,
+            path.clone(),
+            self.client.put(path).body(payload.into()),
+        )
+        .await
     }
 
     pub async fn patch(
@@ -58,11 +241,13 @@ impl Agent {
         path: impl Into<String>,
         payload: impl Into<surf::Body>,
     ) -> Result<surf::Response, surf::Error> {
-        self.client
-            .patch(path.into())
-            .header("User-Agent", self.user_agent.clone())
-            .body(payload.into())
-            .await
+        let path = path.into();
+        self.send(
+            surf::http::Method::Patch,
+            path.clone(),
+            self.client.patch(path).body(payload.into()),
+        )
+        .await
     }
 
     pub async fn delete(
@@ -70,11 +255,13 @@ impl Agent {
         path: impl Into<String>,
         payload: impl Into<surf::Body>,
     ) -> Result<surf::Response, surf::Error> {
-        self.client
-            .delete(path.into())
-            .header("User-Agent", self.user_agent.clone())
-            .body(payload.into())
-            .await
+        let path = path.into();
+        self.send(
+            surf::http::Method::Delete,
+            path.clone(),
+            self.client.delete(path).body(payload.into()),
+        )
+        .await
     }
 }
 
@@ -217,4 +404,144 @@ mod tests {
 
         Ok(())
     }
+
+    #[async_std::test]
+    async fn test_agent_metrics() -> surf::Result<()> {
+        let base_url = &mockito::server_url();
+        let path = "/metrics";
+
+        let _m = mockito::mock("GET", path)
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        let agent = Agent::new(base_url);
+        agent.get(path).await?;
+        agent.clone().get(path).await?;
+
+        let metrics = agent.metrics();
+        assert_eq!(metrics.requests, 2);
+        assert!(metrics.latency > Duration::from_secs(0));
+        assert_eq!(metrics.rate_limit_wait, Duration::from_secs(0));
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_agent_endpoint_stats() -> surf::Result<()> {
+        let base_url = &mockito::server_url();
+        let path = "/samples";
+
+        let _m1 = mockito::mock("GET", path)
+            .match_query(mockito::Matcher::Any)
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+        let _m2 = mockito::mock("POST", path)
+            .with_status(surf::StatusCode::NotFound as usize)
+            .create();
+
+        let agent = Agent::new(base_url);
+        agent.get("/samples?page=1").await?;
+        agent.clone().get("/samples?page=2").await?;
+        agent.post(path, "").await?;
+
+        agent.set_stats_paused(true);
+        agent.get(path).await?;
+        agent.set_stats_paused(false);
+
+        let endpoint_stats = agent.endpoint_stats();
+        assert_eq!(endpoint_stats.len(), 2);
+        assert_eq!(endpoint_stats[0].method, "GET");
+        assert_eq!(endpoint_stats[0].path, path);
+        assert_eq!(endpoint_stats[0].requests(), 2);
+        assert_eq!(endpoint_stats[0].errors, 0);
+        assert_eq!(endpoint_stats[1].method, "POST");
+        assert_eq!(endpoint_stats[1].requests(), 1);
+        assert_eq!(endpoint_stats[1].errors, 1);
+        assert_eq!(agent.metrics().requests, 4);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_agent_endpoint_key() -> surf::Result<()> {
+        let base_url = &mockito::server_url();
+
+        let _m = mockito::mock("GET", mockito::Matcher::Regex(r"^/items/\d+$".into()))
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        fn endpoint_key(_method: &str, path: &str) -> String {
+            match path.rsplit_once('/') {
+                Some((prefix, _)) => format!("{}/:id", prefix),
+                None => path.to_string(),
+            }
+        }
+
+        let mut agent = Agent::new(base_url);
+        agent.set_endpoint_key(endpoint_key);
+        for id in 0..1000 {
+            agent.get(format!("/items/{}", id)).await?;
+        }
+
+        let endpoint_stats = agent.endpoint_stats();
+        assert_eq!(endpoint_stats.len(), 1);
+        assert_eq!(endpoint_stats[0].path, "/items/:id");
+        assert_eq!(endpoint_stats[0].requests(), 1000);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_agent_endpoint_stats_cap() -> surf::Result<()> {
+        let base_url = &mockito::server_url();
+
+        let _m = mockito::mock("GET", mockito::Matcher::Regex(r"^/capped/\d+$".into()))
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        let agent = Agent::new(base_url);
+        for id in 0..1000 {
+            agent.get(format!("/capped/{}", id)).await?;
+        }
+
+        let endpoint_stats = agent.endpoint_stats();
+        assert_eq!(endpoint_stats.len(), MAX_ENDPOINTS + 1);
+        let other = endpoint_stats
+            .iter()
+            .find(|stats| stats.path == OTHER_ENDPOINT)
+            .unwrap();
+        assert_eq!(other.requests(), 1000 - MAX_ENDPOINTS);
+        assert_eq!(
+            endpoint_stats
+                .iter()
+                .map(|stats| stats.requests())
+                .sum::<usize>(),
+            1000
+        );
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_agent_rate_limiter() -> surf::Result<()> {
+        let base_url = &mockito::server_url();
+        let path = "/rate_limited";
+
+        let _m = mockito::mock("GET", path)
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        let rate_limiter = RateLimiter::per_second(5.0).unwrap();
+        rate_limiter.acquire().await;
+
+        let mut agent = Agent::new(base_url);
+        agent.set_rate_limiter(rate_limiter);
+        agent.get(path).await?;
+
+        let metrics = agent.metrics();
+        assert_eq!(metrics.requests, 1);
+        assert!(metrics.rate_limit_wait >= Duration::from_millis(150));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,130 @@
+use async_std::task;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+#[derive(Clone, Error, Debug, PartialEq)]
+pub enum RateLimiterError {
+    #[error("rate {rate:?} must be a finite number greater than zero")]
+    InvalidRate { rate: f64 },
+}
+
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    updated_at: Instant,
+    total_wait: Duration,
+}
+
+impl TokenBucket {
+    fn reserve(&mut self) -> Duration {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.updated_at = now;
+
+        // tokens may go negative, which reserves a future slot for this caller
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            return Duration::from_secs(0);
+        }
+
+        let wait = Duration::from_secs_f64(-self.tokens / self.rate);
+        self.total_wait += wait;
+        wait
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<TokenBucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(rate: f64, burst: usize) -> Result<RateLimiter, RateLimiterError> {
+        // a single token must be refillable within a representable wait
+        if !rate.is_finite() || rate <= 0.0 || Duration::try_from_secs_f64(1.0 / rate).is_err() {
+            return Err(RateLimiterError::InvalidRate { rate });
+        }
+
+        let capacity = burst.max(1) as f64;
+
+        Ok(RateLimiter {
+            bucket: Arc::new(Mutex::new(TokenBucket {
+                rate,
+                capacity,
+                tokens: capacity,
+                updated_at: Instant::now(),
+                total_wait: Duration::from_secs(0),
+            })),
+        })
+    }
+
+    pub fn per_second(rate: f64) -> Result<RateLimiter, RateLimiterError> {
+        RateLimiter::new(rate, 1)
+    }
+
+    pub async fn acquire(&self) -> Duration {
+        let wait = self.bucket.lock().unwrap().reserve();
+        if wait > Duration::from_secs(0) {
+            task::sleep(wait).await;
+        }
+        wait
+    }
+
+    pub fn total_wait(&self) -> Duration {
+        self.bucket.lock().unwrap().total_wait
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::agent::rate_limiter::*;
+
+    #[async_std::test]
+    async fn test_acquire_burst() {
+        let rate_limiter = RateLimiter::new(1.0, 3).unwrap();
+
+        assert_eq!(rate_limiter.acquire().await, Duration::from_secs(0));
+        assert_eq!(rate_limiter.acquire().await, Duration::from_secs(0));
+        assert_eq!(rate_limiter.acquire().await, Duration::from_secs(0));
+        assert_eq!(rate_limiter.total_wait(), Duration::from_secs(0));
+    }
+
+    #[async_std::test]
+    async fn test_acquire_wait() {
+        let rate_limiter = RateLimiter::per_second(20.0).unwrap();
+
+        let started_at = Instant::now();
+        for _ in 0..5 {
+            rate_limiter.acquire().await;
+        }
+        let elapsed = started_at.elapsed();
+
+        assert!(elapsed >= Duration::from_millis(190));
+        assert!(rate_limiter.total_wait() >= Duration::from_millis(190));
+    }
+
+    #[async_std::test]
+    async fn test_acquire_shared() {
+        let rate_limiter = RateLimiter::per_second(20.0).unwrap();
+        let cloned = rate_limiter.clone();
+
+        rate_limiter.acquire().await;
+        let wait = cloned.acquire().await;
+
+        assert!(wait > Duration::from_millis(0));
+        assert_eq!(rate_limiter.total_wait(), cloned.total_wait());
+    }
+
+    #[test]
+    fn test_invalid_rate() {
+        for rate in [0.0, -1.0, f64::NAN, f64::INFINITY, f64::MIN_POSITIVE].iter() {
+            assert!(matches!(
+                RateLimiter::per_second(*rate),
+                Err(RateLimiterError::InvalidRate { .. })
+            ));
+        }
+    }
+}
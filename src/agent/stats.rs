@@ -0,0 +1,348 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const LATENCY_SUB_BUCKET_BITS: u32 = 3;
+const LATENCY_SUB_BUCKETS: usize = 1 << LATENCY_SUB_BUCKET_BITS;
+const LATENCY_BUCKETS: usize = 256;
+pub const TIMELINE_BUCKETS: usize = 60;
+pub const MIN_TIMELINE_BUCKET: Duration = Duration::from_millis(100);
+
+// log-linear buckets over microseconds, so each bucket is within 1/8 of its value
+fn latency_bucket(latency: Duration) -> usize {
+    let micros = latency.as_micros().min(u64::MAX as u128) as u64;
+    if micros < LATENCY_SUB_BUCKETS as u64 {
+        return micros as usize;
+    }
+
+    let shift = 63 - micros.leading_zeros() - LATENCY_SUB_BUCKET_BITS;
+    let sub_bucket = (micros >> shift) as usize - LATENCY_SUB_BUCKETS;
+    ((shift as usize + 1) * LATENCY_SUB_BUCKETS + sub_bucket).min(LATENCY_BUCKETS - 1)
+}
+
+fn latency_bucket_upper_bound(index: usize) -> Duration {
+    if index < LATENCY_SUB_BUCKETS {
+        return Duration::from_micros(index as u64 + 1);
+    }
+
+    let shift = index / LATENCY_SUB_BUCKETS - 1;
+    let sub_bucket = index % LATENCY_SUB_BUCKETS;
+    Duration::from_micros(((LATENCY_SUB_BUCKETS + sub_bucket + 1) as u64) << shift)
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LatencyHistogram {
+    counts: Vec<usize>,
+    count: usize,
+    max: Duration,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> LatencyHistogram {
+        LatencyHistogram {
+            counts: vec![0; LATENCY_BUCKETS],
+            count: 0,
+            max: Duration::from_secs(0),
+        }
+    }
+
+    pub fn record(&mut self, latency: Duration) {
+        self.counts[latency_bucket(latency)] += 1;
+        self.count += 1;
+        self.max = self.max.max(latency);
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        if self.count == 0 {
+            return Duration::from_secs(0);
+        }
+
+        let rank = ((percentile / 100.0 * self.count as f64).ceil() as usize).clamp(1, self.count);
+        let mut seen = 0;
+        for (index, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return latency_bucket_upper_bound(index).min(self.max);
+            }
+        }
+        self.max
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> LatencyHistogram {
+        LatencyHistogram::new()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimelineBucket {
+    pub offset: Duration,
+    pub requests: usize,
+    pub errors: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RequestTimeline {
+    bucket: Duration,
+    buckets: Vec<TimelineBucket>,
+}
+
+impl RequestTimeline {
+    pub fn new() -> RequestTimeline {
+        RequestTimeline {
+            bucket: MIN_TIMELINE_BUCKET,
+            buckets: Vec::new(),
+        }
+    }
+
+    pub fn bucket(&self) -> Duration {
+        self.bucket
+    }
+
+    pub fn buckets(&self) -> Vec<TimelineBucket> {
+        self.buckets.clone()
+    }
+
+    pub fn record(&mut self, offset: Duration, is_error: bool) {
+        self.add(offset, 1, if is_error { 1 } else { 0 });
+    }
+
+    pub fn merge(&mut self, other: &RequestTimeline) {
+        while self.bucket < other.bucket {
+            self.widen();
+        }
+        for timeline_bucket in &other.buckets {
+            self.add(
+                timeline_bucket.offset,
+                timeline_bucket.requests,
+                timeline_bucket.errors,
+            );
+        }
+    }
+
+    fn add(&mut self, offset: Duration, requests: usize, errors: usize) {
+        let mut index = (offset.as_nanos() / self.bucket.as_nanos()) as usize;
+        while index >= TIMELINE_BUCKETS {
+            self.widen();
+            index = (offset.as_nanos() / self.bucket.as_nanos()) as usize;
+        }
+
+        while self.buckets.len() <= index {
+            self.buckets.push(TimelineBucket {
+                offset: self.bucket * self.buckets.len() as u32,
+                requests: 0,
+                errors: 0,
+            });
+        }
+        self.buckets[index].requests += requests;
+        self.buckets[index].errors += errors;
+    }
+
+    // doubles the bucket width and folds each pair of buckets into one
+    fn widen(&mut self) {
+        self.bucket *= 2;
+
+        let buckets = std::mem::take(&mut self.buckets);
+        for (index, pair) in buckets.chunks(2).enumerate() {
+            self.buckets.push(TimelineBucket {
+                offset: self.bucket * index as u32,
+                requests: pair
+                    .iter()
+                    .map(|timeline_bucket| timeline_bucket.requests)
+                    .sum(),
+                errors: pair
+                    .iter()
+                    .map(|timeline_bucket| timeline_bucket.errors)
+                    .sum(),
+            });
+        }
+    }
+}
+
+impl Default for RequestTimeline {
+    fn default() -> RequestTimeline {
+        RequestTimeline::new()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EndpointStats {
+    pub method: String,
+    pub path: String,
+    pub errors: usize,
+    pub latency: LatencyHistogram,
+    pub timeline: RequestTimeline,
+}
+
+impl EndpointStats {
+    pub fn new(method: impl Into<String>, path: impl Into<String>) -> EndpointStats {
+        EndpointStats {
+            method: method.into(),
+            path: path.into(),
+            errors: 0,
+            latency: LatencyHistogram::new(),
+            timeline: RequestTimeline::new(),
+        }
+    }
+
+    pub fn requests(&self) -> usize {
+        self.latency.count()
+    }
+
+    pub fn record(&mut self, offset: Duration, latency: Duration, status: Option<u16>) {
+        let is_error = status.is_none_or(|status| status >= 400);
+        if is_error {
+            self.errors += 1;
+        }
+        self.latency.record(latency);
+        self.timeline.record(offset, is_error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::agent::stats::*;
+
+    #[test]
+    fn test_latency_bucket() {
+        assert_eq!(latency_bucket(Duration::from_micros(0)), 0);
+        assert_eq!(latency_bucket(Duration::from_micros(7)), 7);
+        assert_eq!(latency_bucket(Duration::from_micros(8)), 8);
+        assert_eq!(latency_bucket(Duration::from_micros(16)), 16);
+        assert_eq!(
+            latency_bucket(Duration::from_secs(1_000_000)),
+            LATENCY_BUCKETS - 1
+        );
+
+        let mut previous = 0;
+        for micros in 0..100_000 {
+            let latency = Duration::from_micros(micros);
+            let index = latency_bucket(latency);
+            assert!(index >= previous);
+            assert!(latency < latency_bucket_upper_bound(index));
+            previous = index;
+        }
+    }
+
+    #[test]
+    fn test_latency_histogram() {
+        let mut histogram = LatencyHistogram::new();
+        assert_eq!(histogram.percentile(50.0), Duration::from_secs(0));
+
+        for ms in 1..=100 {
+            histogram.record(Duration::from_millis(ms));
+        }
+
+        assert_eq!(histogram.count(), 100);
+        assert_eq!(histogram.max(), Duration::from_millis(100));
+        for (percentile, expected) in [(50.0, 50), (90.0, 90), (99.0, 99)].iter() {
+            let actual = histogram.percentile(*percentile);
+            assert!(actual >= Duration::from_millis(*expected));
+            assert!(actual.as_secs_f64() <= *expected as f64 / 1000.0 * 1.125);
+        }
+        assert_eq!(histogram.percentile(100.0), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_request_timeline() {
+        let mut timeline = RequestTimeline::new();
+        timeline.record(Duration::from_millis(50), false);
+        timeline.record(Duration::from_millis(90), true);
+        timeline.record(Duration::from_millis(250), false);
+
+        assert_eq!(timeline.bucket(), MIN_TIMELINE_BUCKET);
+        assert_eq!(
+            timeline.buckets(),
+            vec![
+                TimelineBucket {
+                    offset: Duration::from_millis(0),
+                    requests: 2,
+                    errors: 1
+                },
+                TimelineBucket {
+                    offset: Duration::from_millis(100),
+                    requests: 0,
+                    errors: 0
+                },
+                TimelineBucket {
+                    offset: Duration::from_millis(200),
+                    requests: 1,
+                    errors: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_request_timeline_widen() {
+        let mut timeline = RequestTimeline::new();
+        for second in 0..600 {
+            timeline.record(Duration::from_secs(second), second % 10 == 0);
+        }
+
+        assert_eq!(timeline.bucket(), Duration::from_millis(12_800));
+        assert!(timeline.buckets().len() <= TIMELINE_BUCKETS);
+        assert_eq!(
+            timeline
+                .buckets()
+                .iter()
+                .map(|timeline_bucket| timeline_bucket.requests)
+                .sum::<usize>(),
+            600
+        );
+        assert_eq!(
+            timeline
+                .buckets()
+                .iter()
+                .map(|timeline_bucket| timeline_bucket.errors)
+                .sum::<usize>(),
+            60
+        );
+    }
+
+    #[test]
+    fn test_request_timeline_merge() {
+        let mut short = RequestTimeline::new();
+        short.record(Duration::from_millis(150), false);
+
+        let mut long = RequestTimeline::new();
+        long.record(Duration::from_secs(10), true);
+
+        let mut timeline = RequestTimeline::new();
+        timeline.merge(&short);
+        timeline.merge(&long);
+
+        assert_eq!(timeline.bucket(), long.bucket());
+        assert_eq!(timeline.buckets()[0].requests, 1);
+        assert_eq!(timeline.buckets().last().unwrap().errors, 1);
+    }
+
+    #[test]
+    fn test_endpoint_stats() {
+        let mut stats = EndpointStats::new("GET", "/posts");
+        stats.record(
+            Duration::from_millis(0),
+            Duration::from_millis(12),
+            Some(200),
+        );
+        stats.record(
+            Duration::from_millis(10),
+            Duration::from_millis(30),
+            Some(500),
+        );
+        stats.record(Duration::from_millis(20), Duration::from_millis(5), None);
+
+        assert_eq!(stats.requests(), 3);
+        assert_eq!(stats.errors, 2);
+        assert_eq!(stats.latency.max(), Duration::from_millis(30));
+        assert_eq!(stats.timeline.buckets()[0].requests, 3);
+    }
+}
@@ -1,13 +1,40 @@
+pub mod budget;
+pub mod initialize;
+pub mod load;
+pub mod phase;
+pub mod readiness;
 pub mod scenario;
+pub mod scoring;
 pub mod step;
+pub mod timing;
 
+use crate::agent::rate_limiter::*;
+use crate::agent::stats::*;
 use crate::agent::*;
+use crate::benchmark::budget::*;
+use crate::benchmark::initialize::*;
+use crate::benchmark::load::*;
+use crate::benchmark::phase::*;
+use crate::benchmark::readiness::*;
 use crate::benchmark::scenario::*;
+use crate::benchmark::scoring::*;
+use crate::benchmark::timing::*;
 use crate::errors::*;
 use crate::score::*;
 
+use async_std::channel::{bounded, unbounded, Receiver, RecvError, Sender};
+use async_std::future;
 use async_std::task;
-use crossbeam_channel::{bounded, unbounded, Receiver, RecvError, Sender};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::Poll;
+use std::time::{Duration, Instant};
 
 enum LoadScenarioWorkMessage {
     Start(BenchmarkScenario),
@@ -17,9 +44,204 @@ enum LoadScenarioWorkMessage {
 enum LoadScenarioResultMessage {
     Processed(BenchmarkScenarioResult),
     Canceled(BenchmarkScenarioResult),
+    Dropped(BenchmarkScenarioResult),
+    TimedOut(BenchmarkScenarioResult),
+    Timeline(TimelineEvent),
     Stopped,
 }
 
+struct LoadScenarioReport {
+    latency: Duration,
+    is_error: bool,
+    follow_up_scenarios: Vec<BenchmarkScenario>,
+}
+
+enum LoadScenarioProcessorEvent {
+    Work(Result<LoadScenarioWorkMessage, RecvError>),
+    Report(Result<LoadScenarioReport, RecvError>),
+}
+
+async fn recv_load_scenario_processor_event(
+    work_receiver: &Receiver<LoadScenarioWorkMessage>,
+    report_receiver: &Receiver<LoadScenarioReport>,
+) -> LoadScenarioProcessorEvent {
+    let mut work = pin!(work_receiver.recv());
+    let mut report = pin!(report_receiver.recv());
+
+    std::future::poll_fn(|cx| {
+        if let Poll::Ready(report) = report.as_mut().poll(cx) {
+            return Poll::Ready(LoadScenarioProcessorEvent::Report(report));
+        }
+        if let Poll::Ready(work) = work.as_mut().poll(cx) {
+            return Poll::Ready(LoadScenarioProcessorEvent::Work(work));
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+const STAGE_TICK: Duration = Duration::from_millis(10);
+
+#[derive(Default)]
+struct PhaseOutcome {
+    scenario_results: Vec<BenchmarkScenarioResult>,
+    unprocessed_results: Vec<BenchmarkScenarioResult>,
+}
+
+#[derive(Default)]
+struct LoadScenarioOutcome {
+    scenario_results: Vec<BenchmarkScenarioResult>,
+    unprocessed_results: Vec<BenchmarkScenarioResult>,
+    dropped_starts: usize,
+    timeline: Vec<TimelineEvent>,
+}
+
+struct LoadScenarioProcessor {
+    agent: Agent,
+    score: Score,
+    errors: Errors,
+    load_model: LoadModel,
+    deadline: Option<Duration>,
+    timeout: Option<Duration>,
+    started_at: Instant,
+    result_sender: Sender<LoadScenarioResultMessage>,
+    report_sender: Sender<LoadScenarioReport>,
+    parallels: usize,
+    adaptive_controller: Option<AdaptiveController>,
+    follow_up_scenarios: VecDeque<BenchmarkScenario>,
+    ongoing_workers: usize,
+}
+
+impl LoadScenarioProcessor {
+    fn is_running(&self) -> bool {
+        self.deadline
+            .is_none_or(|deadline| self.started_at.elapsed() < deadline)
+    }
+
+    fn limit(&mut self) -> Option<usize> {
+        if !self.is_running() {
+            return None;
+        }
+
+        let elapsed = self.started_at.elapsed();
+        match &self.load_model {
+            LoadModel::Closed => Some(self.parallels),
+            LoadModel::Open(_) => None,
+            LoadModel::Staged(load_profile) => load_profile
+                .stage_at(elapsed)
+                .map(|(_, stage)| stage.parallels),
+            LoadModel::Adaptive(_) => {
+                let adaptive_controller = self.adaptive_controller.as_mut()?;
+                if let Some(event) = adaptive_controller.adjust(elapsed) {
+                    let _ = self
+                        .result_sender
+                        .try_send(LoadScenarioResultMessage::Timeline(event));
+                }
+                Some(adaptive_controller.parallels())
+            }
+        }
+    }
+
+    fn start(&mut self, scenario: BenchmarkScenario) {
+        let scenario_name = scenario.clone().name;
+        let mut result = BenchmarkScenarioResult::new(scenario_name);
+
+        if !self.is_running() {
+            result.set_status(ScenarioStatus::Canceled);
+            let _ = self
+                .result_sender
+                .try_send(LoadScenarioResultMessage::Canceled(result));
+            return;
+        }
+
+        let mut stage = None;
+        match &self.load_model {
+            LoadModel::Open(arrival_rate) => {
+                if self.ongoing_workers >= arrival_rate.max_in_flight() {
+                    result.set_status(ScenarioStatus::Skipped);
+                    let _ = self
+                        .result_sender
+                        .try_send(LoadScenarioResultMessage::Dropped(result));
+                    return;
+                }
+            }
+            LoadModel::Staged(load_profile) => {
+                stage = load_profile
+                    .stage_at(self.started_at.elapsed())
+                    .map(|(index, _)| index);
+            }
+            LoadModel::Closed | LoadModel::Adaptive(_) => {}
+        }
+
+        let result_sender = self.result_sender.clone();
+        let report_sender = self.report_sender.clone();
+
+        self.ongoing_workers += 1;
+
+        let agent = self.agent.clone();
+        let score = self.score.clone();
+        let errors = self.errors.clone();
+        let timeout = self.timeout;
+        let started_at = self.started_at;
+        let _worker = task::spawn(async move {
+            let scenario_started_at = Instant::now();
+            let scenario_name = scenario.name.clone();
+            let run = scenario.run(agent, score, errors);
+            let result = match timeout {
+                Some(timeout) => {
+                    let remaining = timeout.saturating_sub(started_at.elapsed());
+                    future::timeout(remaining, run).await.ok()
+                }
+                None => Some(run.await),
+            };
+
+            let (message, report) = match result {
+                Some(mut result) => {
+                    if let Some(stage) = stage {
+                        result.set_stage(stage);
+                    }
+
+                    let mut follow_up_scenarios = result.take_follow_up_scenarios();
+                    if result.is_failure() {
+                        follow_up_scenarios.clear();
+                    }
+
+                    let report = LoadScenarioReport {
+                        latency: scenario_started_at.elapsed(),
+                        is_error: result.is_failure() || result.total_lose() > 0,
+                        follow_up_scenarios,
+                    };
+                    (LoadScenarioResultMessage::Processed(result), report)
+                }
+                None => {
+                    let mut result = BenchmarkScenarioResult::new(scenario_name);
+                    if let Some(stage) = stage {
+                        result.set_stage(stage);
+                    }
+                    result.set_status(ScenarioStatus::TimedOut);
+
+                    let report = LoadScenarioReport {
+                        latency: scenario_started_at.elapsed(),
+                        is_error: true,
+                        follow_up_scenarios: Vec::new(),
+                    };
+                    (LoadScenarioResultMessage::TimedOut(result), report)
+                }
+            };
+            let _ = result_sender.send(message).await;
+            let _ = report_sender.send(report).await;
+        });
+    }
+
+    fn finish(&mut self, report: LoadScenarioReport) {
+        self.ongoing_workers -= 1;
+        if let Some(adaptive_controller) = &mut self.adaptive_controller {
+            adaptive_controller.record(report.latency, report.is_error);
+        }
+        self.follow_up_scenarios.extend(report.follow_up_scenarios);
+    }
+}
+
 pub struct Benchmark {
     agent: Agent,
     score: Score,
@@ -28,6 +250,17 @@ pub struct Benchmark {
     load_scenarios: Vec<BenchmarkScenario>,
     validation_scenarios: Vec<BenchmarkScenario>,
     parallels: usize,
+    load_model: LoadModel,
+    load_timeout: Option<Duration>,
+    scoring_policy: Arc<dyn ScoringPolicy>,
+    error_budget: ErrorBudget,
+    phase_rules: Vec<(BenchmarkPhase, PhaseRule)>,
+    custom_phases: Vec<CustomPhase>,
+    phase_order: Vec<BenchmarkPhase>,
+    unscored_phases: Vec<BenchmarkPhase>,
+    warm_up: Option<WarmUp>,
+    initialize: Option<Initialize>,
+    readiness: Option<Readiness>,
 }
 
 impl Benchmark {
@@ -40,9 +273,102 @@ impl Benchmark {
             load_scenarios: Vec::new(),
             validation_scenarios: Vec::new(),
             parallels: parallels,
+            load_model: LoadModel::Closed,
+            load_timeout: None,
+            scoring_policy: Arc::new(Subtraction),
+            error_budget: ErrorBudget::new(),
+            phase_rules: Vec::new(),
+            custom_phases: Vec::new(),
+            phase_order: vec![
+                BenchmarkPhase::Prepare,
+                BenchmarkPhase::Load,
+                BenchmarkPhase::Validation,
+            ],
+            unscored_phases: Vec::new(),
+            warm_up: None,
+            initialize: None,
+            readiness: None,
+        }
+    }
+
+    pub fn set_load_model(&mut self, load_model: LoadModel) {
+        self.load_model = load_model;
+    }
+
+    pub fn set_load_timeout(&mut self, load_timeout: Duration) {
+        self.load_timeout = Some(load_timeout);
+    }
+
+    fn load_deadline(&self) -> Option<Duration> {
+        match (self.load_model.deadline(), self.load_timeout) {
+            (Some(deadline), Some(load_timeout)) => Some(deadline.min(load_timeout)),
+            (deadline, load_timeout) => deadline.or(load_timeout),
+        }
+    }
+
+    pub fn set_scoring_policy(&mut self, scoring_policy: impl ScoringPolicy + 'static) {
+        self.scoring_policy = Arc::new(scoring_policy);
+    }
+
+    pub fn set_error_budget(&mut self, error_budget: ErrorBudget) {
+        self.error_budget = error_budget;
+    }
+
+    pub fn set_phase_rule(&mut self, phase: BenchmarkPhase, rule: PhaseRule) {
+        self.phase_rules
+            .retain(|(rule_phase, _)| *rule_phase != phase);
+        self.phase_rules.push((phase, rule));
+    }
+
+    pub fn set_phase_scored(&mut self, phase: BenchmarkPhase, scored: bool) {
+        self.unscored_phases
+            .retain(|unscored_phase| *unscored_phase != phase);
+        if !scored {
+            self.unscored_phases.push(phase);
+        }
+    }
+
+    pub fn add_custom_phase(&mut self, custom_phase: CustomPhase) {
+        let phase = custom_phase.phase();
+        self.set_phase_scored(phase.clone(), custom_phase.is_scored());
+        if !self.phase_order.contains(&phase) {
+            self.phase_order.push(phase);
+        }
+        self.custom_phases.push(custom_phase);
+    }
+
+    pub fn set_readiness(&mut self, readiness: Readiness) {
+        self.readiness = Some(readiness);
+    }
+
+    pub fn set_initialize(&mut self, initialize: Initialize) {
+        self.initialize = Some(initialize);
+        if !self.phase_order.contains(&BenchmarkPhase::Initialize) {
+            self.phase_order.insert(0, BenchmarkPhase::Initialize);
         }
     }
 
+    pub fn set_warm_up(&mut self, warm_up: WarmUp) {
+        self.warm_up = Some(warm_up);
+        self.set_phase_scored(BenchmarkPhase::WarmUp, false);
+        if !self.phase_order.contains(&BenchmarkPhase::WarmUp) {
+            let index = self
+                .phase_order
+                .iter()
+                .position(|phase| *phase == BenchmarkPhase::Load)
+                .unwrap_or(self.phase_order.len());
+            self.phase_order.insert(index, BenchmarkPhase::WarmUp);
+        }
+    }
+
+    pub fn set_phase_order(&mut self, phase_order: Vec<BenchmarkPhase>) {
+        self.phase_order = phase_order;
+    }
+
+    pub fn set_rate_limiter(&mut self, rate_limiter: RateLimiter) {
+        self.agent.set_global_rate_limiter(rate_limiter);
+    }
+
     pub fn add_prepare_scenario(&mut self, scenario: BenchmarkScenario) {
         self.prepare_scenarios.push(scenario);
     }
@@ -69,16 +395,78 @@ impl Benchmark {
         scenario_results
     }
 
-    async fn spawn_load_scenario_source(&self, work_sender: Sender<LoadScenarioWorkMessage>) {
+    async fn spawn_load_scenario_source(
+        &self,
+        work_sender: Sender<LoadScenarioWorkMessage>,
+        started_at: Instant,
+    ) {
         let load_scenarios = self.load_scenarios.clone();
+        let load_model = self.load_model.clone();
+        let deadline = self.load_deadline();
 
         let _source = task::spawn(async move {
-            for scenario in load_scenarios {
-                let scenario_name = scenario.clone().name;
-                let _ = work_sender.send(LoadScenarioWorkMessage::Start(scenario));
-                log::debug!("[Source] send start {}", scenario_name);
+            match load_model {
+                LoadModel::Closed => {
+                    for scenario in load_scenarios {
+                        let scenario_name = scenario.clone().name;
+                        let _ = work_sender
+                            .send(LoadScenarioWorkMessage::Start(scenario))
+                            .await;
+                        log::debug!("[Source] send start {}", scenario_name);
+                    }
+                }
+                LoadModel::Open(arrival_rate) => {
+                    let mut scenarios = load_scenarios.iter().cycle();
+
+                    for stage in arrival_rate.stages() {
+                        let interval = Duration::from_secs_f64(1.0 / stage.rate);
+                        let stage_started_at = Instant::now();
+                        let mut arrivals = 0;
+
+                        loop {
+                            let scheduled_at = interval * arrivals;
+                            if scheduled_at >= stage.duration {
+                                break;
+                            }
+                            if deadline.is_some_and(|deadline| started_at.elapsed() >= deadline) {
+                                break;
+                            }
+
+                            let elapsed = stage_started_at.elapsed();
+                            if scheduled_at > elapsed {
+                                task::sleep(scheduled_at - elapsed).await;
+                            }
+                            arrivals += 1;
+
+                            if let Some(scenario) = scenarios.next() {
+                                let scenario = scenario.clone();
+                                let scenario_name = scenario.clone().name;
+                                let _ = work_sender
+                                    .send(LoadScenarioWorkMessage::Start(scenario))
+                                    .await;
+                                log::debug!("[Source] send start {}", scenario_name);
+                            }
+                        }
+                    }
+                }
+                LoadModel::Staged(_) | LoadModel::Adaptive(_) => {
+                    let deadline = deadline.unwrap_or_default();
+
+                    for scenario in load_scenarios.iter().cycle() {
+                        if started_at.elapsed() >= deadline {
+                            break;
+                        }
+
+                        let scenario = scenario.clone();
+                        let scenario_name = scenario.clone().name;
+                        let _ = work_sender
+                            .send(LoadScenarioWorkMessage::Start(scenario))
+                            .await;
+                        log::debug!("[Source] send start {}", scenario_name);
+                    }
+                }
             }
-            let _ = work_sender.send(LoadScenarioWorkMessage::Stop);
+            let _ = work_sender.send(LoadScenarioWorkMessage::Stop).await;
             log::debug!("[Source] send stop");
         });
     }
@@ -87,73 +475,85 @@ impl Benchmark {
         &self,
         work_receiver: Receiver<LoadScenarioWorkMessage>,
         result_sender: Sender<LoadScenarioResultMessage>,
+        started_at: Instant,
     ) {
-        let parallels = self.parallels.clone();
-        let agent = self.agent.clone();
-        let score = self.score.clone();
-        let errors = self.errors.clone();
+        let (report_sender, report_receiver) = unbounded();
+        let mut processor = LoadScenarioProcessor {
+            agent: self.agent.clone(),
+            score: self.score.clone(),
+            errors: self.errors.clone(),
+            load_model: self.load_model.clone(),
+            deadline: self.load_deadline(),
+            timeout: self.load_timeout,
+            started_at,
+            result_sender,
+            report_sender,
+            parallels: self.parallels.max(1),
+            adaptive_controller: match &self.load_model {
+                LoadModel::Adaptive(adaptive) => Some(AdaptiveController::new(adaptive.clone())),
+                _ => None,
+            },
+            follow_up_scenarios: VecDeque::new(),
+            ongoing_workers: 0,
+        };
 
         let _processor = task::spawn(async move {
-            let (parallel_sender, parallel_receiver) = bounded(parallels);
-            let (processor_result_sender, processor_result_receiver) = unbounded();
             let mut is_receive_exit = false;
-            let mut ongoing_workers = 0;
 
             loop {
-                crossbeam_channel::select! {
-                    recv(work_receiver) -> scenario => {
-                        match scenario {
-                            Ok(LoadScenarioWorkMessage::Start(scenario)) => {
-                                let scenario_name = scenario.clone().name;
-                                let result = BenchmarkScenarioResult::new(scenario_name);
-
-                                if is_receive_exit {
-                                    let _ = result_sender.send(LoadScenarioResultMessage::Canceled(result));
-                                    continue;
-                                }
-
-                                let result_sender = result_sender.clone();
-                                let processor_result_sender = processor_result_sender.clone();
-                                let parallel_receiver = parallel_receiver.clone();
-
-                                let _ = parallel_sender.send(());
-                                ongoing_workers += 1;
-
-                                let agent = agent.clone();
-                                let score = score.clone();
-                                let errors = errors.clone();
-                                let _worker = task::spawn(async move {
-                                    // task::sleep(std::time::Duration::from_secs(1)).await;
-                                    let result = scenario
-                                        .run(agent.clone(), score.clone(), errors.clone())
-                                        .await;
-                                    let _ = result_sender.send(LoadScenarioResultMessage::Processed(result));
-                                    let _ = processor_result_sender.send(());
-                                    let _ = parallel_receiver.recv();
-                                });
-                            },
-                            Ok(LoadScenarioWorkMessage::Stop) => {
-                                is_receive_exit = true;
-                                if ongoing_workers == 0 {
-                                    let _ = result_sender.send(LoadScenarioResultMessage::Stopped);
-                                    break;
+                if let Some(limit) = processor.limit() {
+                    if processor.ongoing_workers >= limit {
+                        match processor.load_model {
+                            LoadModel::Closed => {
+                                if let Ok(report) = report_receiver.recv().await {
+                                    processor.finish(report);
                                 }
                             }
-                            Err(RecvError) => {},
-                        }
-                    },
-                    recv(processor_result_receiver) -> msg => {
-                        match msg {
-                            Ok(()) => {
-                                ongoing_workers -= 1;
-                                if is_receive_exit && ongoing_workers == 0 {
-                                    let _ = result_sender.send(LoadScenarioResultMessage::Stopped);
-                                    break;
+                            _ => {
+                                if let Ok(Ok(report)) =
+                                    future::timeout(STAGE_TICK, report_receiver.recv()).await
+                                {
+                                    processor.finish(report);
                                 }
-                            },
-                            Err(RecvError) => {},
+                            }
                         }
-                    },
+                        continue;
+                    }
+                }
+
+                if let Some(scenario) = processor.follow_up_scenarios.pop_front() {
+                    processor.start(scenario);
+                    continue;
+                }
+
+                if is_receive_exit {
+                    if processor.ongoing_workers == 0 {
+                        let _ = processor
+                            .result_sender
+                            .send(LoadScenarioResultMessage::Stopped)
+                            .await;
+                        break;
+                    }
+                    if let Ok(report) = report_receiver.recv().await {
+                        processor.finish(report);
+                    }
+                    continue;
+                }
+
+                match recv_load_scenario_processor_event(&work_receiver, &report_receiver).await {
+                    LoadScenarioProcessorEvent::Work(Ok(LoadScenarioWorkMessage::Start(
+                        scenario,
+                    ))) => {
+                        processor.start(scenario);
+                    }
+                    LoadScenarioProcessorEvent::Work(Ok(LoadScenarioWorkMessage::Stop))
+                    | LoadScenarioProcessorEvent::Work(Err(RecvError)) => {
+                        is_receive_exit = true;
+                    }
+                    LoadScenarioProcessorEvent::Report(Ok(report)) => {
+                        processor.finish(report);
+                    }
+                    LoadScenarioProcessorEvent::Report(Err(RecvError)) => {}
                 }
             }
         });
@@ -162,41 +562,61 @@ impl Benchmark {
     async fn spawn_load_scenario_consumer(
         &self,
         result_receiver: Receiver<LoadScenarioResultMessage>,
-    ) -> Vec<BenchmarkScenarioResult> {
+    ) -> LoadScenarioOutcome {
         let consumer = task::spawn(async move {
-            let mut scenario_results = Vec::new();
+            let mut outcome = LoadScenarioOutcome::default();
 
             loop {
-                match result_receiver.recv() {
+                match result_receiver.recv().await {
                     Ok(LoadScenarioResultMessage::Processed(result)) => {
                         let scenario_name = result.clone().scenario_name;
-                        scenario_results.push(result);
+                        outcome.scenario_results.push(result);
                         log::debug!("[Consumer] receive processed {}", scenario_name);
                     }
                     Ok(LoadScenarioResultMessage::Canceled(result)) => {
                         let scenario_name = result.clone().scenario_name;
+                        outcome.unprocessed_results.push(result);
                         log::debug!("[Consumer] receive canceled {}", scenario_name);
                     }
-                    Ok(LoadScenarioResultMessage::Stopped) => {
+                    Ok(LoadScenarioResultMessage::Dropped(result)) => {
+                        let scenario_name = result.clone().scenario_name;
+                        outcome.unprocessed_results.push(result);
+                        outcome.dropped_starts += 1;
+                        log::debug!("[Consumer] receive dropped {}", scenario_name);
+                    }
+                    Ok(LoadScenarioResultMessage::TimedOut(result)) => {
+                        let scenario_name = result.clone().scenario_name;
+                        outcome.unprocessed_results.push(result);
+                        log::debug!("[Consumer] receive timed out {}", scenario_name);
+                    }
+                    Ok(LoadScenarioResultMessage::Timeline(event)) => {
+                        log::debug!("[Consumer] receive timeline {:?}", event);
+                        outcome.timeline.push(event);
+                    }
+                    Ok(LoadScenarioResultMessage::Stopped) | Err(RecvError) => {
                         log::debug!("[Consumer] receive stoped");
                         break;
                     }
-                    Err(RecvError) => {}
                 }
             }
 
-            scenario_results
+            outcome
         });
 
         consumer.await
     }
 
-    async fn start_load_scenario(&self) -> Vec<BenchmarkScenarioResult> {
-        let (work_sender, work_receiver) = unbounded();
+    async fn start_load_scenario(&self) -> LoadScenarioOutcome {
+        let (work_sender, work_receiver) = match self.load_model {
+            LoadModel::Staged(_) | LoadModel::Adaptive(_) => bounded(1),
+            _ => unbounded(),
+        };
         let (result_sender, result_receiver) = unbounded();
+        let started_at = Instant::now();
 
-        self.spawn_load_scenario_source(work_sender).await;
-        self.spawn_load_scenario_processor(work_receiver, result_sender)
+        self.spawn_load_scenario_source(work_sender, started_at)
+            .await;
+        self.spawn_load_scenario_processor(work_receiver, result_sender, started_at)
             .await;
         self.spawn_load_scenario_consumer(result_receiver).await
     }
@@ -215,102 +635,792 @@ impl Benchmark {
         scenario_results
     }
 
-    pub async fn start(&self) -> BenchmarkResult {
-        let mut benchmark_result = BenchmarkResult::new();
-
-        let _: Vec<_> = self
-            .start_prepare_scenario()
-            .await
+    async fn start_load_phase(&self, benchmark_result: &mut BenchmarkResult) {
+        let load_outcome = self.start_load_scenario().await;
+        let _: Vec<_> = load_outcome
+            .scenario_results
+            .into_iter()
+            .map(|mut result| {
+                result.set_phase(BenchmarkPhase::Load);
+                benchmark_result.add_scenario_result(result)
+            })
+            .collect();
+        let _: Vec<_> = load_outcome
+            .unprocessed_results
+            .into_iter()
+            .map(|mut result| {
+                result.set_phase(BenchmarkPhase::Load);
+                benchmark_result.add_unprocessed_result(result)
+            })
+            .collect();
+        benchmark_result.set_dropped_starts(load_outcome.dropped_starts);
+        let _: Vec<_> = load_outcome
+            .timeline
             .into_iter()
-            .map(|result| benchmark_result.add_scenario_result(result))
+            .map(|event| benchmark_result.add_timeline_event(event))
             .collect();
+    }
 
-        let _: Vec<_> = self
-            .start_load_scenario()
-            .await
+    async fn start_custom_phase(&self, custom_phase: &CustomPhase) -> PhaseOutcome {
+        let mut outcome = PhaseOutcome::default();
+
+        match custom_phase.execution_mode() {
+            ExecutionMode::Sequential => {
+                for scenario in custom_phase.scenarios() {
+                    outcome.scenario_results.push(
+                        scenario
+                            .run(self.agent.clone(), self.score.clone(), self.errors.clone())
+                            .await,
+                    );
+                }
+            }
+            ExecutionMode::Parallel(workers) => {
+                let (work_sender, work_receiver) = unbounded();
+                for scenario in custom_phase.scenarios() {
+                    let _ = work_sender.send(scenario).await;
+                }
+                work_sender.close();
+
+                let mut workers_handles = Vec::new();
+                for _ in 0..workers.max(1) {
+                    let work_receiver: Receiver<BenchmarkScenario> = work_receiver.clone();
+                    let agent = self.agent.clone();
+                    let score = self.score.clone();
+                    let errors = self.errors.clone();
+                    workers_handles.push(task::spawn(async move {
+                        let mut scenario_results = Vec::new();
+                        while let Ok(scenario) = work_receiver.recv().await {
+                            scenario_results.push(
+                                scenario
+                                    .run(agent.clone(), score.clone(), errors.clone())
+                                    .await,
+                            );
+                        }
+                        scenario_results
+                    }));
+                }
+                for worker in workers_handles {
+                    outcome.scenario_results.append(&mut worker.await);
+                }
+            }
+            ExecutionMode::TimeBoxed(duration) => {
+                let started_at = Instant::now();
+
+                for scenario in custom_phase.scenarios() {
+                    let mut result = BenchmarkScenarioResult::new(&scenario.name);
+                    let remaining = duration.saturating_sub(started_at.elapsed());
+                    if remaining.is_zero() {
+                        result.set_status(ScenarioStatus::Canceled);
+                        outcome.unprocessed_results.push(result);
+                        continue;
+                    }
+
+                    let run =
+                        scenario.run(self.agent.clone(), self.score.clone(), self.errors.clone());
+                    match future::timeout(remaining, run).await {
+                        Ok(result) => outcome.scenario_results.push(result),
+                        Err(_) => {
+                            result.set_status(ScenarioStatus::TimedOut);
+                            outcome.unprocessed_results.push(result);
+                        }
+                    }
+                }
+            }
+        }
+
+        outcome
+    }
+
+    async fn start_warm_up_phase(&self) -> PhaseOutcome {
+        let mut outcome = PhaseOutcome::default();
+        let warm_up = match self.warm_up {
+            Some(warm_up) if !self.load_scenarios.is_empty() => warm_up,
+            _ => return outcome,
+        };
+
+        let started_at = Instant::now();
+        let next_index = Arc::new(AtomicUsize::new(0));
+
+        let mut workers = Vec::new();
+        for _ in 0..self.parallels.max(1) {
+            let load_scenarios = self.load_scenarios.clone();
+            let next_index = next_index.clone();
+            let agent = self.agent.clone();
+            let score = self.score.clone();
+            let errors = self.errors.clone();
+            workers.push(task::spawn(async move {
+                let mut scenario_results = Vec::new();
+                loop {
+                    let index = next_index.fetch_add(1, Ordering::SeqCst);
+                    let is_finished = match warm_up {
+                        WarmUp::Duration(duration) => started_at.elapsed() >= duration,
+                        WarmUp::Count(count) => index >= count,
+                    };
+                    if is_finished {
+                        break;
+                    }
+
+                    let scenario = load_scenarios[index % load_scenarios.len()].clone();
+                    let mut result = scenario
+                        .run(agent.clone(), score.clone(), errors.clone())
+                        .await;
+                    result.discard_except_failures();
+                    scenario_results.push(result);
+                }
+                scenario_results
+            }));
+        }
+        for worker in workers {
+            outcome.scenario_results.append(&mut worker.await);
+        }
+
+        outcome
+    }
+
+    async fn start_phase(&self, phase: &BenchmarkPhase, benchmark_result: &mut BenchmarkResult) {
+        let outcome = match phase {
+            BenchmarkPhase::Initialize => {
+                if let Some(initialize) = &self.initialize {
+                    if let Err(error) = initialize.run(&self.agent).await {
+                        benchmark_result.set_initialize_error(error);
+                    }
+                }
+                return;
+            }
+            BenchmarkPhase::Prepare => PhaseOutcome {
+                scenario_results: self.start_prepare_scenario().await,
+                unprocessed_results: Vec::new(),
+            },
+            BenchmarkPhase::Load => {
+                self.start_load_phase(benchmark_result).await;
+                return;
+            }
+            BenchmarkPhase::Validation => PhaseOutcome {
+                scenario_results: self.start_validation_scenario().await,
+                unprocessed_results: Vec::new(),
+            },
+            BenchmarkPhase::WarmUp => {
+                self.agent.set_stats_paused(true);
+                let outcome = self.start_warm_up_phase().await;
+                self.agent.set_stats_paused(false);
+                outcome
+            }
+            BenchmarkPhase::Custom(_) => {
+                match self
+                    .custom_phases
+                    .iter()
+                    .find(|custom_phase| custom_phase.phase() == *phase)
+                {
+                    Some(custom_phase) => self.start_custom_phase(custom_phase).await,
+                    None => {
+                        log::warn!("Unknown phase {}", phase.name());
+                        PhaseOutcome::default()
+                    }
+                }
+            }
+        };
+
+        let _: Vec<_> = outcome
+            .scenario_results
+            .into_iter()
+            .map(|mut result| {
+                result.set_phase(phase.clone());
+                benchmark_result.add_scenario_result(result)
+            })
+            .collect();
+        let _: Vec<_> = outcome
+            .unprocessed_results
             .into_iter()
-            .map(|result| benchmark_result.add_scenario_result(result))
+            .map(|mut result| {
+                result.set_phase(phase.clone());
+                benchmark_result.add_unprocessed_result(result)
+            })
             .collect();
+    }
 
-        let _: Vec<_> = self
-            .start_validation_scenario()
-            .await
+    fn skip_phase(&self, phase: &BenchmarkPhase, benchmark_result: &mut BenchmarkResult) {
+        benchmark_result.add_skipped_phase(phase.clone());
+        if *phase == BenchmarkPhase::Load {
+            for scenario in &self.load_scenarios {
+                let mut result = BenchmarkScenarioResult::new(&scenario.name);
+                result.set_phase(BenchmarkPhase::Load);
+                result.set_status(ScenarioStatus::Skipped);
+                benchmark_result.add_unprocessed_result(result);
+            }
+        }
+    }
+
+    pub async fn start(&self) -> BenchmarkResult {
+        let mut benchmark_result = BenchmarkResult::new();
+        benchmark_result.set_scoring_policy(self.scoring_policy.clone());
+        for (phase, rule) in &self.phase_rules {
+            benchmark_result.set_phase_rule(phase.clone(), *rule);
+        }
+        for phase in &self.unscored_phases {
+            benchmark_result.set_phase_scored(phase.clone(), false);
+        }
+        if let Some(readiness) = &self.readiness {
+            if let Err(error) = readiness.wait(&self.agent).await {
+                benchmark_result.set_readiness_error(error);
+            }
+        }
+        let started_at = self.agent.elapsed();
+        let mut ended_at = started_at;
+
+        for phase in &self.phase_order {
+            benchmark_result.add_phase(phase.clone());
+
+            let abort_reason = match (
+                benchmark_result.readiness_error(),
+                benchmark_result.initialize_error(),
+            ) {
+                (Some(error), _) => Some(error.to_string()),
+                (None, Some(error)) => Some(error.to_string()),
+                (None, None) => None,
+            };
+            if let Some(abort_reason) = abort_reason {
+                log::warn!("Skip {} phase because {}", phase.name(), abort_reason);
+                self.skip_phase(phase, &mut benchmark_result);
+                continue;
+            }
+
+            if *phase == BenchmarkPhase::Load {
+                if let Some(failed_phase) = benchmark_result
+                    .phase_results()
+                    .iter()
+                    .find(|phase_result| phase_result.fails_run())
+                {
+                    log::warn!(
+                        "Skip load phase because {} phase failed",
+                        failed_phase.phase().name()
+                    );
+                    self.skip_phase(phase, &mut benchmark_result);
+                    continue;
+                }
+            }
+
+            let phase_started_at = ended_at;
+            let requests = self.agent.metrics().requests;
+            self.start_phase(phase, &mut benchmark_result).await;
+            ended_at = self.agent.elapsed();
+            benchmark_result
+                .set_phase_requests(phase.clone(), self.agent.metrics().requests - requests);
+            benchmark_result.add_phase_timing(PhaseTiming {
+                phase: phase.clone(),
+                timing: Timing::new(phase_started_at, ended_at),
+            });
+        }
+        benchmark_result.set_timing(Timing::new(started_at, ended_at));
+
+        benchmark_result.set_agent_metrics(self.agent.metrics());
+        benchmark_result.set_endpoint_stats(self.agent.endpoint_stats());
+
+        let budget_breaches = self.error_budget.check(
+            &benchmark_result.scored_error_groups(),
+            benchmark_result.scored_requests(),
+        );
+        let _: Vec<_> = budget_breaches
             .into_iter()
-            .map(|result| benchmark_result.add_scenario_result(result))
+            .map(|breach| benchmark_result.add_budget_breach(breach))
             .collect();
 
         benchmark_result
     }
 }
 
+#[derive(Deserialize)]
 pub struct BenchmarkResult {
     scenario_results: Vec<BenchmarkScenarioResult>,
+    unprocessed_results: Vec<BenchmarkScenarioResult>,
+    agent_metrics: AgentMetrics,
+    phase_requests: Vec<(BenchmarkPhase, usize)>,
+    endpoint_stats: Vec<EndpointStats>,
+    dropped_starts: usize,
+    timeline: Vec<TimelineEvent>,
+    budget_breaches: Vec<BudgetBreach>,
+    initialize_error: Option<InitializeError>,
+    readiness_error: Option<ReadinessError>,
+    timing: Timing,
+    phase_timings: Vec<PhaseTiming>,
+    phases: Vec<BenchmarkPhase>,
+    phase_rules: Vec<(BenchmarkPhase, PhaseRule)>,
+    unscored_phases: Vec<BenchmarkPhase>,
+    skipped_phases: Vec<BenchmarkPhase>,
+    #[serde(skip, default = "default_scoring_policy")]
+    scoring_policy: Arc<dyn ScoringPolicy>,
+    // the policy cannot be serialized, so a deserialized result keeps its computed total
+    #[serde(default)]
+    total_score: Option<isize>,
 }
 
 impl BenchmarkResult {
     pub fn new() -> BenchmarkResult {
         BenchmarkResult {
             scenario_results: Vec::new(),
+            unprocessed_results: Vec::new(),
+            agent_metrics: AgentMetrics::default(),
+            phase_requests: Vec::new(),
+            endpoint_stats: Vec::new(),
+            dropped_starts: 0,
+            timeline: Vec::new(),
+            budget_breaches: Vec::new(),
+            initialize_error: None,
+            readiness_error: None,
+            timing: Timing::default(),
+            phase_timings: Vec::new(),
+            phases: Vec::new(),
+            phase_rules: Vec::new(),
+            unscored_phases: Vec::new(),
+            skipped_phases: Vec::new(),
+            scoring_policy: default_scoring_policy(),
+            total_score: None,
+        }
+    }
+
+    pub fn set_scoring_policy(&mut self, scoring_policy: Arc<dyn ScoringPolicy>) {
+        self.scoring_policy = scoring_policy;
+        self.total_score = None;
+    }
+
+    pub fn scoring_input(&self) -> ScoringInput {
+        let mut input = ScoringInput::new(self.scored_requests());
+        for phase_result in self.scored_phase_results() {
+            for result in &phase_result.details() {
+                input.add_scenario_result(result);
+            }
+            if phase_result.penalty_point() > 0 {
+                input.add_phase_penalty(phase_result.phase(), phase_result.penalty_point());
+            }
         }
+        input
     }
 
     pub fn details(&self) -> Vec<BenchmarkScenarioResult> {
         self.scenario_results.clone()
     }
 
-    pub fn add_scenario_result(&mut self, result: BenchmarkScenarioResult) {
-        self.scenario_results.push(result);
+    pub fn unprocessed_details(&self) -> Vec<BenchmarkScenarioResult> {
+        self.unprocessed_results.clone()
     }
 
-    pub fn total_score(&self) -> isize {
-        self.total_gain() - self.total_lose()
+    pub fn add_unprocessed_result(&mut self, result: BenchmarkScenarioResult) {
+        self.unprocessed_results.push(result);
     }
 
-    pub fn total_gain(&self) -> isize {
-        self.scenario_results
+    pub fn scenario_counts(&self) -> ScenarioCounts {
+        let mut counts = ScenarioCounts::new();
+        for result in self
+            .scenario_results
             .iter()
-            .fold(0, |total, result| total + result.total_gain())
+            .chain(&self.unprocessed_results)
+        {
+            counts.add(result.status());
+        }
+        counts
     }
 
-    pub fn total_lose(&self) -> isize {
-        self.scenario_results
-            .iter()
-            .fold(0, |total, result| total + result.total_lose())
+    pub fn completion_ratio(&self) -> f64 {
+        self.scenario_counts().completion_ratio()
     }
 
-    pub fn is_success(&self) -> bool {
-        !self.is_failure()
+    pub fn agent_metrics(&self) -> AgentMetrics {
+        self.agent_metrics.clone()
     }
 
-    pub fn is_failure(&self) -> bool {
-        self.scenario_results
+    pub fn set_agent_metrics(&mut self, agent_metrics: AgentMetrics) {
+        self.agent_metrics = agent_metrics;
+    }
+
+    pub fn phase_requests(&self, phase: &BenchmarkPhase) -> usize {
+        self.phase_requests
             .iter()
-            .any(|result| result.is_failure())
+            .find(|(requests_phase, _)| requests_phase == phase)
+            .map(|(_, requests)| *requests)
+            .unwrap_or_default()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::benchmark::step::*;
-    use crate::benchmark::*;
+    pub fn set_phase_requests(&mut self, phase: BenchmarkPhase, requests: usize) {
+        self.phase_requests
+            .retain(|(requests_phase, _)| *requests_phase != phase);
+        self.phase_requests.push((phase, requests));
+    }
 
-    #[async_std::test]
-    async fn test_benchmark() -> Result<(), ()> {
-        let base_url = &mockito::server_url();
-        let path = "/dummy";
+    pub fn warm_up_requests(&self) -> usize {
+        self.phase_requests(&BenchmarkPhase::WarmUp)
+    }
 
-        let _m = mockito::mock("GET", path)
-            .with_status(surf::StatusCode::Ok as usize)
-            .create();
+    pub fn scored_requests(&self) -> usize {
+        let unscored_requests: usize = self
+            .phase_requests
+            .iter()
+            .filter(|(phase, _)| !self.is_phase_scored(phase))
+            .map(|(_, requests)| requests)
+            .sum();
+        self.agent_metrics
+            .requests
+            .saturating_sub(unscored_requests)
+    }
 
-        let agent = Agent::new(base_url);
+    pub fn endpoint_stats(&self) -> Vec<EndpointStats> {
+        self.endpoint_stats.clone()
+    }
 
-        let mut score = Score::new();
-        score.add_point_table("a", 1);
-        score.add_point_table("b", 2);
-        score.add_point_table("c", 3);
+    pub fn set_endpoint_stats(&mut self, endpoint_stats: Vec<EndpointStats>) {
+        self.endpoint_stats = endpoint_stats;
+    }
 
-        let errors = Errors::new();
+    pub fn dropped_starts(&self) -> usize {
+        self.dropped_starts
+    }
+
+    pub fn set_dropped_starts(&mut self, dropped_starts: usize) {
+        self.dropped_starts = dropped_starts;
+    }
+
+    pub fn timeline(&self) -> Vec<TimelineEvent> {
+        self.timeline.clone()
+    }
+
+    pub fn add_timeline_event(&mut self, event: TimelineEvent) {
+        self.timeline.push(event);
+    }
+
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+    }
+
+    pub fn phase_timings(&self) -> Vec<PhaseTiming> {
+        self.phase_timings.clone()
+    }
+
+    pub fn add_phase_timing(&mut self, phase_timing: PhaseTiming) {
+        self.phase_timings.push(phase_timing);
+    }
+
+    pub fn phase_rule(&self, phase: &BenchmarkPhase) -> PhaseRule {
+        self.phase_rules
+            .iter()
+            .find(|(rule_phase, _)| rule_phase == phase)
+            .map(|(_, rule)| *rule)
+            .unwrap_or_default()
+    }
+
+    pub fn set_phase_rule(&mut self, phase: BenchmarkPhase, rule: PhaseRule) {
+        self.phase_rules
+            .retain(|(rule_phase, _)| *rule_phase != phase);
+        self.phase_rules.push((phase, rule));
+    }
+
+    pub fn is_phase_scored(&self, phase: &BenchmarkPhase) -> bool {
+        !self.unscored_phases.contains(phase)
+    }
+
+    pub fn set_phase_scored(&mut self, phase: BenchmarkPhase, scored: bool) {
+        self.unscored_phases
+            .retain(|unscored_phase| *unscored_phase != phase);
+        if !scored {
+            self.unscored_phases.push(phase);
+        }
+    }
+
+    pub fn add_phase(&mut self, phase: BenchmarkPhase) {
+        if !self.phases.contains(&phase) {
+            self.phases.push(phase);
+        }
+    }
+
+    pub fn phases(&self) -> Vec<BenchmarkPhase> {
+        let mut phases: Vec<BenchmarkPhase> = Vec::new();
+        let candidates = self
+            .phases
+            .iter()
+            .cloned()
+            .chain(vec![
+                BenchmarkPhase::Prepare,
+                BenchmarkPhase::Load,
+                BenchmarkPhase::Validation,
+            ])
+            .chain(
+                self.phase_timings
+                    .iter()
+                    .map(|phase_timing| phase_timing.phase.clone()),
+            )
+            .chain(
+                self.scenario_results
+                    .iter()
+                    .map(|result| result.phase().unwrap_or(BenchmarkPhase::Load)),
+            );
+        for phase in candidates {
+            if !phases.contains(&phase) {
+                phases.push(phase);
+            }
+        }
+        phases
+    }
+
+    pub fn skipped_phases(&self) -> Vec<BenchmarkPhase> {
+        self.skipped_phases.clone()
+    }
+
+    pub fn add_skipped_phase(&mut self, phase: BenchmarkPhase) {
+        self.skipped_phases.push(phase);
+    }
+
+    pub fn phase_results(&self) -> Vec<PhaseResult> {
+        self.phases()
+            .into_iter()
+            .filter_map(|phase| self.phase_result(phase))
+            .collect()
+    }
+
+    fn scored_phase_results(&self) -> Vec<PhaseResult> {
+        self.phase_results()
+            .into_iter()
+            .filter(|phase_result| phase_result.is_scored())
+            .collect()
+    }
+
+    pub fn phase_result(&self, phase: BenchmarkPhase) -> Option<PhaseResult> {
+        let mut phase_result = PhaseResult::new(phase.clone(), self.phase_rule(&phase));
+        phase_result.set_scored(self.is_phase_scored(&phase));
+        phase_result.set_skipped(self.skipped_phases.contains(&phase));
+        if let Some(phase_timing) = self
+            .phase_timings
+            .iter()
+            .find(|phase_timing| phase_timing.phase == phase)
+        {
+            phase_result.set_timing(phase_timing.timing);
+        }
+        let _: Vec<_> = self
+            .scenario_results
+            .iter()
+            .filter(|result| result.phase().unwrap_or(BenchmarkPhase::Load) == phase)
+            .map(|result| phase_result.add_scenario_result(result.clone()))
+            .collect();
+
+        if phase_result.is_skipped()
+            || phase_result.timing().is_some()
+            || !phase_result.details().is_empty()
+        {
+            Some(phase_result)
+        } else {
+            None
+        }
+    }
+
+    pub fn budget_breaches(&self) -> Vec<BudgetBreach> {
+        self.budget_breaches.clone()
+    }
+
+    pub fn add_budget_breach(&mut self, breach: BudgetBreach) {
+        self.budget_breaches.push(breach);
+    }
+
+    pub fn initialize_error(&self) -> Option<InitializeError> {
+        self.initialize_error.clone()
+    }
+
+    pub fn set_initialize_error(&mut self, initialize_error: InitializeError) {
+        self.initialize_error = Some(initialize_error);
+    }
+
+    pub fn readiness_error(&self) -> Option<ReadinessError> {
+        self.readiness_error.clone()
+    }
+
+    pub fn set_readiness_error(&mut self, readiness_error: ReadinessError) {
+        self.readiness_error = Some(readiness_error);
+    }
+
+    pub fn add_scenario_result(&mut self, result: BenchmarkScenarioResult) {
+        self.scenario_results.push(result);
+    }
+
+    pub fn total_score(&self) -> isize {
+        match self.total_score {
+            Some(total_score) => total_score,
+            None => self.scoring_policy.score(&self.scoring_input()),
+        }
+    }
+
+    pub fn total_gain(&self) -> isize {
+        self.scored_phase_results()
+            .iter()
+            .fold(0, |total, phase_result| total + phase_result.total_gain())
+    }
+
+    pub fn total_lose(&self) -> isize {
+        self.scored_phase_results()
+            .iter()
+            .fold(0, |total, phase_result| total + phase_result.total_lose())
+    }
+
+    pub fn error_records(&self) -> Vec<ErrorRecord> {
+        self.scenario_results
+            .iter()
+            .flat_map(|result| result.error_records())
+            .collect()
+    }
+
+    pub fn error_groups(&self) -> Vec<ErrorGroup> {
+        group_error_records(self.error_records().iter())
+    }
+
+    fn scored_error_groups(&self) -> Vec<ErrorGroup> {
+        let records: Vec<_> = self
+            .scenario_results
+            .iter()
+            .filter(|result| {
+                result
+                    .phase()
+                    .is_none_or(|phase| self.is_phase_scored(&phase))
+            })
+            .flat_map(|result| result.error_records())
+            .collect();
+        group_error_records(records.iter())
+    }
+
+    pub fn participant_messages(&self, limit: usize) -> Vec<String> {
+        participant_messages(&self.error_groups(), limit)
+    }
+
+    pub fn breakdown(&self) -> ScoreBreakdown {
+        merge_breakdowns(
+            self.scored_phase_results()
+                .iter()
+                .flat_map(|phase_result| phase_result.details())
+                .map(|result| result.breakdown()),
+        )
+    }
+
+    pub fn is_success(&self) -> bool {
+        !self.is_failure()
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.readiness_error.is_some()
+            || self.initialize_error.is_some()
+            || !self.budget_breaches.is_empty()
+            || self
+                .phase_results()
+                .iter()
+                .any(|phase_result| phase_result.fails_run())
+    }
+
+    pub fn configuration_errors(&self) -> Vec<ScoreError> {
+        merge_configuration_errors(
+            self.scenario_results
+                .iter()
+                .flat_map(|result| result.configuration_errors()),
+        )
+    }
+}
+
+impl Serialize for BenchmarkResult {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("BenchmarkResult", 17)?;
+        state.serialize_field("scenario_results", &self.scenario_results)?;
+        state.serialize_field("unprocessed_results", &self.unprocessed_results)?;
+        state.serialize_field("agent_metrics", &self.agent_metrics)?;
+        state.serialize_field("phase_requests", &self.phase_requests)?;
+        state.serialize_field("endpoint_stats", &self.endpoint_stats)?;
+        state.serialize_field("dropped_starts", &self.dropped_starts)?;
+        state.serialize_field("timeline", &self.timeline)?;
+        state.serialize_field("budget_breaches", &self.budget_breaches)?;
+        state.serialize_field("initialize_error", &self.initialize_error)?;
+        state.serialize_field("readiness_error", &self.readiness_error)?;
+        state.serialize_field("timing", &self.timing)?;
+        state.serialize_field("phase_timings", &self.phase_timings)?;
+        state.serialize_field("phases", &self.phases)?;
+        state.serialize_field("phase_rules", &self.phase_rules)?;
+        state.serialize_field("unscored_phases", &self.unscored_phases)?;
+        state.serialize_field("skipped_phases", &self.skipped_phases)?;
+        state.serialize_field("total_score", &self.total_score())?;
+        state.end()
+    }
+}
+
+fn default_scoring_policy() -> Arc<dyn ScoringPolicy> {
+    Arc::new(Subtraction)
+}
+
+impl PartialEq for BenchmarkResult {
+    fn eq(&self, other: &Self) -> bool {
+        self.scenario_results == other.scenario_results
+            && self.unprocessed_results == other.unprocessed_results
+            && self.agent_metrics == other.agent_metrics
+            && self.phase_requests == other.phase_requests
+            && self.endpoint_stats == other.endpoint_stats
+            && self.dropped_starts == other.dropped_starts
+            && self.timeline == other.timeline
+            && self.budget_breaches == other.budget_breaches
+            && self.initialize_error == other.initialize_error
+            && self.readiness_error == other.readiness_error
+            && self.timing == other.timing
+            && self.phase_timings == other.phase_timings
+            && self.phases == other.phases
+            && self.phase_rules == other.phase_rules
+            && self.unscored_phases == other.unscored_phases
+            && self.skipped_phases == other.skipped_phases
+            && self.total_score() == other.total_score()
+    }
+}
+
+impl fmt::Debug for BenchmarkResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BenchmarkResult")
+            .field("scenario_results", &self.scenario_results)
+            .field("unprocessed_results", &self.unprocessed_results)
+            .field("agent_metrics", &self.agent_metrics)
+            .field("phase_requests", &self.phase_requests)
+            .field("endpoint_stats", &self.endpoint_stats)
+            .field("dropped_starts", &self.dropped_starts)
+            .field("timeline", &self.timeline)
+            .field("budget_breaches", &self.budget_breaches)
+            .field("initialize_error", &self.initialize_error)
+            .field("readiness_error", &self.readiness_error)
+            .field("timing", &self.timing)
+            .field("phase_timings", &self.phase_timings)
+            .field("phases", &self.phases)
+            .field("phase_rules", &self.phase_rules)
+            .field("unscored_phases", &self.unscored_phases)
+            .field("skipped_phases", &self.skipped_phases)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for BenchmarkResult {
+    fn default() -> BenchmarkResult {
+        BenchmarkResult::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::benchmark::step::*;
+    use crate::benchmark::*;
+
+    #[async_std::test]
+    async fn test_benchmark() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+        let path = "/dummy";
+
+        let _m = mockito::mock("GET", path)
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        let agent = Agent::new(base_url);
+
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+        score.add_point_table("b", 2);
+        score.add_point_table("c", 3);
+
+        let errors = Errors::new();
 
         let parallels = 8;
 
@@ -390,6 +1500,34 @@ mod tests {
         assert_eq!(benchmark_result.is_success(), true);
         assert_eq!(benchmark_result.is_failure(), false);
 
+        let error_groups = benchmark_result.error_groups();
+        assert_eq!(error_groups.len(), 3);
+        assert_eq!(error_groups[0].code, "error_a");
+        assert_eq!(error_groups[0].count, 3);
+        assert_eq!(
+            benchmark_result.participant_messages(1),
+            vec![
+                "[penalty] error_a x 3 (-3)".to_string(),
+                "... and 2 more".to_string()
+            ]
+        );
+
+        let breakdown = benchmark_result.breakdown();
+        assert_eq!(
+            breakdown["a"],
+            PointBreakdown {
+                count: 3,
+                subtotal: 3
+            }
+        );
+        assert_eq!(
+            breakdown["c"],
+            PointBreakdown {
+                count: 3,
+                subtotal: 9
+            }
+        );
+
         Ok(())
     }
 
@@ -560,4 +1698,1314 @@ mod tests {
 
         Ok(())
     }
+
+    #[async_std::test]
+    async fn test_benchmark_rate_limiter() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+        let path = "/dummy";
+
+        let _m = mockito::mock("GET", path)
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        let agent = Agent::new(base_url);
+
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+
+        let errors = Errors::new();
+
+        let parallels = 8;
+
+        let rate_limiter = RateLimiter::per_second(5.0).unwrap();
+        rate_limiter.acquire().await;
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+        benchmark.set_rate_limiter(rate_limiter);
+
+        fn step(agent: Agent, mut score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                let response = agent.get("/dummy").await;
+                assert_eq!(response.unwrap().status(), surf::StatusCode::Ok);
+
+                score.record("a");
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark_scenario = BenchmarkScenario::new("scenario");
+        benchmark_scenario.add_benchmark_step(step);
+        benchmark.add_load_scenario(benchmark_scenario);
+
+        let benchmark_result = benchmark.start().await;
+        assert_eq!(benchmark_result.total_gain(), 1);
+        assert_eq!(benchmark_result.agent_metrics().requests, 1);
+        assert!(
+            benchmark_result.agent_metrics().rate_limit_wait
+                >= std::time::Duration::from_millis(150)
+        );
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_open_load_model() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+        let path = "/dummy";
+
+        let _m = mockito::mock("GET", path)
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        let agent = Agent::new(base_url);
+
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+
+        let errors = Errors::new();
+
+        let parallels = 1;
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+        benchmark.set_load_model(LoadModel::Open(
+            ArrivalRate::constant(50.0, Duration::from_millis(200)).unwrap(),
+        ));
+
+        fn step(agent: Agent, mut score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                let response = agent.get("/dummy").await;
+                assert_eq!(response.unwrap().status(), surf::StatusCode::Ok);
+
+                score.record("a");
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark_scenario1 = BenchmarkScenario::new("scenario1");
+        benchmark_scenario1.add_benchmark_step(step);
+
+        let mut benchmark_scenario2 = BenchmarkScenario::new("scenario2");
+        benchmark_scenario2.add_benchmark_step(step);
+
+        benchmark.add_load_scenario(benchmark_scenario1);
+        benchmark.add_load_scenario(benchmark_scenario2);
+
+        let benchmark_result = benchmark.start().await;
+        assert_eq!(benchmark_result.details().len(), 10);
+        assert_eq!(benchmark_result.details()[0].scenario_name(), "scenario1");
+        assert_eq!(benchmark_result.details()[1].scenario_name(), "scenario2");
+        assert_eq!(benchmark_result.total_gain(), 10);
+        assert_eq!(benchmark_result.dropped_starts(), 0);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_open_load_model_dropped_starts() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let score = Score::new();
+
+        let errors = Errors::new();
+
+        let parallels = 1;
+
+        let mut arrival_rate = ArrivalRate::constant(100.0, Duration::from_millis(100)).unwrap();
+        arrival_rate.set_max_in_flight(2);
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+        benchmark.set_load_model(LoadModel::Open(arrival_rate));
+
+        fn step(_agent: Agent, score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                task::sleep(Duration::from_millis(500)).await;
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark_scenario = BenchmarkScenario::new("scenario");
+        benchmark_scenario.add_benchmark_step(step);
+
+        benchmark.add_load_scenario(benchmark_scenario);
+
+        let benchmark_result = benchmark.start().await;
+        assert_eq!(benchmark_result.details().len(), 2);
+        assert_eq!(benchmark_result.dropped_starts(), 8);
+        assert_eq!(benchmark_result.unprocessed_details().len(), 8);
+        assert!(benchmark_result
+            .unprocessed_details()
+            .iter()
+            .all(|result| result.status() == ScenarioStatus::Skipped
+                && result.phase() == Some(BenchmarkPhase::Load)));
+        assert_eq!(
+            benchmark_result.scenario_counts(),
+            ScenarioCounts {
+                processed: 2,
+                canceled: 0,
+                timed_out: 0,
+                skipped: 8,
+            }
+        );
+        assert_eq!(benchmark_result.completion_ratio(), 0.2);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_load_timeout_scenario_status() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let score = Score::new();
+
+        let errors = Errors::new();
+
+        let parallels = 1;
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+        benchmark.set_load_timeout(Duration::from_millis(100));
+
+        fn step(_agent: Agent, score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                task::sleep(Duration::from_millis(150)).await;
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        for _ in 0..3 {
+            let mut benchmark_scenario = BenchmarkScenario::new("scenario");
+            benchmark_scenario.add_benchmark_step(step);
+            benchmark.add_load_scenario(benchmark_scenario);
+        }
+
+        let started_at = Instant::now();
+        let benchmark_result = benchmark.start().await;
+        assert!(started_at.elapsed() < Duration::from_millis(150));
+        assert!(benchmark_result.details().is_empty());
+        assert_eq!(
+            benchmark_result
+                .unprocessed_details()
+                .iter()
+                .filter(|result| result.status() == ScenarioStatus::TimedOut)
+                .count(),
+            1
+        );
+        assert_eq!(
+            benchmark_result.scenario_counts(),
+            ScenarioCounts {
+                processed: 0,
+                canceled: 2,
+                timed_out: 1,
+                skipped: 0,
+            }
+        );
+        assert_eq!(benchmark_result.completion_ratio(), 0.0);
+        assert_eq!(benchmark_result.dropped_starts(), 0);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_staged_load_model() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let score = Score::new();
+
+        let errors = Errors::new();
+
+        let parallels = 1;
+
+        let mut load_profile = LoadProfile::new();
+        load_profile.add_stage(1, Duration::from_millis(200));
+        load_profile.add_stage(4, Duration::from_millis(200));
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+        benchmark.set_load_model(LoadModel::Staged(load_profile));
+
+        fn step(_agent: Agent, score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                task::sleep(Duration::from_millis(50)).await;
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark_scenario = BenchmarkScenario::new("scenario");
+        benchmark_scenario.add_benchmark_step(step);
+
+        benchmark.add_load_scenario(benchmark_scenario);
+
+        let benchmark_result = benchmark.start().await;
+        let stage0 = benchmark_result
+            .details()
+            .iter()
+            .filter(|result| result.stage() == Some(0))
+            .count();
+        let stage1 = benchmark_result
+            .details()
+            .iter()
+            .filter(|result| result.stage() == Some(1))
+            .count();
+
+        assert!((2..=4).contains(&stage0));
+        assert!(stage1 > stage0 * 2);
+        assert_eq!(benchmark_result.details().len(), stage0 + stage1);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_adaptive_load_model() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let score = Score::new();
+
+        let errors = Errors::new();
+
+        let parallels = 1;
+
+        let mut adaptive = AdaptiveConcurrency::new(Duration::from_millis(500));
+        adaptive.set_parallels(1, 1, 4);
+        adaptive.set_interval(Duration::from_millis(100));
+        adaptive.set_max_latency(Duration::from_secs(1));
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+        benchmark.set_load_model(LoadModel::Adaptive(adaptive));
+
+        fn step(_agent: Agent, score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                task::sleep(Duration::from_millis(10)).await;
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark_scenario = BenchmarkScenario::new("scenario");
+        benchmark_scenario.add_benchmark_step(step);
+
+        benchmark.add_load_scenario(benchmark_scenario);
+
+        let benchmark_result = benchmark.start().await;
+        let adjustments: Vec<_> = benchmark_result
+            .timeline()
+            .into_iter()
+            .map(|event| match event {
+                TimelineEvent::ConcurrencyAdjusted { from, to, .. } => (from, to),
+            })
+            .collect();
+
+        assert_eq!(adjustments, vec![(1, 2), (2, 3), (3, 4)]);
+        assert!(benchmark_result.details().len() > 10);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_adaptive_load_model_back_off() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let score = Score::new();
+
+        let errors = Errors::new();
+
+        let parallels = 1;
+
+        let mut adaptive = AdaptiveConcurrency::new(Duration::from_millis(250));
+        adaptive.set_parallels(4, 1, 4);
+        adaptive.set_interval(Duration::from_millis(100));
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+        benchmark.set_load_model(LoadModel::Adaptive(adaptive));
+
+        fn step(_agent: Agent, score: Score, mut errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                task::sleep(Duration::from_millis(10)).await;
+
+                errors.record(BenchmarkError::Penalty {
+                    cause: "error".into(),
+                    point: 1,
+                });
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark_scenario = BenchmarkScenario::new("scenario");
+        benchmark_scenario.add_benchmark_step(step);
+
+        benchmark.add_load_scenario(benchmark_scenario);
+
+        let benchmark_result = benchmark.start().await;
+        let adjustments: Vec<_> = benchmark_result
+            .timeline()
+            .into_iter()
+            .map(|event| match event {
+                TimelineEvent::ConcurrencyAdjusted { from, to, .. } => (from, to),
+            })
+            .collect();
+
+        assert_eq!(adjustments, vec![(4, 2), (2, 1)]);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_follow_up_scenarios() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+        let path = "/dummy";
+
+        let _m = mockito::mock("GET", path)
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        let agent = Agent::new(base_url);
+
+        let mut score = Score::new();
+        score.add_point_table("signup", 1);
+        score.add_point_table("session", 2);
+
+        let errors = Errors::new();
+
+        let parallels = 2;
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+
+        fn session_step(agent: Agent, mut score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                let response = agent.get("/dummy").await;
+                assert_eq!(response.unwrap().status(), surf::StatusCode::Ok);
+
+                score.record("session");
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        fn signup_step(agent: Agent, mut score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                let response = agent.get("/dummy").await;
+                assert_eq!(response.unwrap().status(), surf::StatusCode::Ok);
+
+                score.record("signup");
+
+                let mut session_scenario = BenchmarkScenario::new("session");
+                session_scenario.add_benchmark_step(session_step);
+
+                let mut result = BenchmarkStepResult::new(score, errors);
+                result.add_follow_up_scenario(session_scenario);
+                result
+            })
+        }
+
+        for _ in 0..3 {
+            let mut signup_scenario = BenchmarkScenario::new("signup");
+            signup_scenario.add_benchmark_step(signup_step);
+            benchmark.add_load_scenario(signup_scenario);
+        }
+
+        let benchmark_result = benchmark.start().await;
+        let sessions = benchmark_result
+            .details()
+            .iter()
+            .filter(|result| result.scenario_name() == "session")
+            .count();
+
+        assert_eq!(benchmark_result.details().len(), 6);
+        assert_eq!(sessions, 3);
+        assert_eq!(benchmark_result.total_gain(), 9);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_follow_up_scenarios_deadline() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let score = Score::new();
+
+        let errors = Errors::new();
+
+        let parallels = 2;
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+        benchmark.set_load_timeout(Duration::from_millis(200));
+
+        fn step(_agent: Agent, score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                task::sleep(Duration::from_millis(20)).await;
+
+                let mut chain_scenario = BenchmarkScenario::new("chain");
+                chain_scenario.add_benchmark_step(step);
+
+                let mut result = BenchmarkStepResult::new(score, errors);
+                result.add_follow_up_scenario(chain_scenario);
+                result
+            })
+        }
+
+        let mut benchmark_scenario = BenchmarkScenario::new("chain");
+        benchmark_scenario.add_benchmark_step(step);
+
+        benchmark.add_load_scenario(benchmark_scenario);
+
+        let benchmark_result = benchmark.start().await;
+        let chains = benchmark_result.details().len();
+
+        assert!((5..=10).contains(&chains));
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_result_configuration_errors() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+
+        let errors = Errors::new();
+
+        let parallels = 8;
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+
+        fn step(_agent: Agent, mut score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                score.record("a");
+                score.record("typo");
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark_scenario1 = BenchmarkScenario::new("scenario1");
+        benchmark_scenario1.add_benchmark_step(step);
+
+        let mut benchmark_scenario2 = BenchmarkScenario::new("scenario2");
+        benchmark_scenario2.add_benchmark_step(step);
+
+        benchmark.add_prepare_scenario(benchmark_scenario1);
+        benchmark.add_load_scenario(benchmark_scenario2);
+
+        let benchmark_result = benchmark.start().await;
+        assert!(benchmark_result.is_success());
+        assert_eq!(benchmark_result.total_gain(), 2);
+        assert_eq!(
+            benchmark_result.configuration_errors(),
+            vec![ScoreError::UnknownPoint {
+                name: "typo".into(),
+                count: 2
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_scoring_policy() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+
+        let errors = Errors::new();
+
+        let parallels = 8;
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+        benchmark.set_scoring_policy(ClampAtZero(Subtraction));
+
+        fn step(_agent: Agent, mut score: Score, mut errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                score.record("a");
+
+                errors.record(BenchmarkError::Penalty {
+                    cause: "error_a".into(),
+                    point: 3,
+                });
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark_scenario1 = BenchmarkScenario::new("scenario1");
+        benchmark_scenario1.add_benchmark_step(step);
+
+        let mut benchmark_scenario2 = BenchmarkScenario::new("scenario2");
+        benchmark_scenario2.add_benchmark_step(step);
+
+        benchmark.add_prepare_scenario(benchmark_scenario1);
+        benchmark.add_load_scenario(benchmark_scenario2);
+
+        let benchmark_result = benchmark.start().await;
+        assert_eq!(benchmark_result.total_gain(), 2);
+        assert_eq!(benchmark_result.total_lose(), 6);
+        assert_eq!(benchmark_result.total_score(), 0);
+
+        let scoring_input = benchmark_result.scoring_input();
+        assert_eq!(
+            scoring_input.phases,
+            vec![
+                PhaseCounts {
+                    phase: BenchmarkPhase::Prepare,
+                    gain: 1,
+                    lose: 3,
+                    errors: 1,
+                },
+                PhaseCounts {
+                    phase: BenchmarkPhase::Load,
+                    gain: 1,
+                    lose: 3,
+                    errors: 1,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_result_serde_scoring_policy() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+
+        let errors = Errors::new();
+
+        let parallels = 1;
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+        benchmark.set_scoring_policy(|input: &ScoringInput| input.total_gain() * 10);
+
+        fn step(_agent: Agent, mut score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                score.record("a");
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark_scenario = BenchmarkScenario::new("scenario");
+        benchmark_scenario.add_benchmark_step(step);
+        benchmark.add_prepare_scenario(benchmark_scenario);
+
+        let benchmark_result = benchmark.start().await;
+        assert_eq!(benchmark_result.total_score(), 10);
+
+        let json = serde_json::to_string(&benchmark_result).unwrap();
+        assert!(!json.contains("point_table"));
+
+        let deserialized: BenchmarkResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.total_score(), 10);
+        assert_eq!(deserialized.total_gain(), 1);
+        assert_eq!(deserialized, benchmark_result);
+
+        let mut rescored: BenchmarkResult = serde_json::from_str(&json).unwrap();
+        rescored.set_scoring_policy(Arc::new(Subtraction));
+        assert_eq!(rescored.total_score(), 1);
+        assert_ne!(rescored, benchmark_result);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_result_serde() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+
+        let errors = Errors::new();
+
+        let parallels = 8;
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+
+        fn step(_agent: Agent, mut score: Score, mut errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                score.record("a");
+
+                errors.record(BenchmarkError::Penalty {
+                    cause: "error_a".into(),
+                    point: 1,
+                });
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark_scenario1 = BenchmarkScenario::new("scenario1");
+        benchmark_scenario1.add_benchmark_step(step);
+
+        let mut benchmark_scenario2 = BenchmarkScenario::new("scenario2");
+        benchmark_scenario2.add_benchmark_step(step);
+
+        benchmark.add_prepare_scenario(benchmark_scenario1);
+        benchmark.add_load_scenario(benchmark_scenario2);
+
+        let benchmark_result = benchmark.start().await;
+
+        let json = serde_json::to_string(&benchmark_result).unwrap();
+        let deserialized: BenchmarkResult = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, benchmark_result);
+        assert_eq!(deserialized.total_score(), benchmark_result.total_score());
+        assert_eq!(
+            deserialized.details()[1].phase(),
+            Some(BenchmarkPhase::Load)
+        );
+
+        let phase_timings = benchmark_result.phase_timings();
+        assert_eq!(
+            phase_timings
+                .iter()
+                .map(|phase_timing| phase_timing.phase.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                BenchmarkPhase::Prepare,
+                BenchmarkPhase::Load,
+                BenchmarkPhase::Validation
+            ]
+        );
+        assert!(phase_timings[1].timing.started_at >= phase_timings[0].timing.ended_at);
+        assert!(phase_timings[2].timing.started_at >= phase_timings[1].timing.ended_at);
+        assert_eq!(
+            benchmark_result.timing().started_at,
+            phase_timings[0].timing.started_at
+        );
+        assert_eq!(
+            benchmark_result.timing().ended_at,
+            phase_timings[2].timing.ended_at
+        );
+        assert!(
+            benchmark_result.details()[1].timing().started_at >= phase_timings[1].timing.started_at
+        );
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_error_budget() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+
+        let errors = Errors::new();
+
+        let parallels = 8;
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+
+        let mut error_budget = ErrorBudget::new();
+        error_budget.set_max_penalty_point(2);
+        benchmark.set_error_budget(error_budget);
+
+        fn step(_agent: Agent, mut score: Score, mut errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                score.record("a");
+
+                errors.record(BenchmarkError::Penalty {
+                    cause: "error_a".into(),
+                    point: 1,
+                });
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        for index in 0..3 {
+            let mut benchmark_scenario = BenchmarkScenario::new(format!("scenario{}", index));
+            benchmark_scenario.add_benchmark_step(step);
+            benchmark.add_load_scenario(benchmark_scenario);
+        }
+
+        let benchmark_result = benchmark.start().await;
+        assert!(benchmark_result
+            .details()
+            .iter()
+            .all(|result| result.is_success()));
+        assert!(benchmark_result.is_failure());
+        assert_eq!(
+            benchmark_result.budget_breaches(),
+            vec![BudgetBreach::PenaltyPoint {
+                limit: 2,
+                actual: 3
+            }]
+        );
+
+        Ok(())
+    }
+
+    fn success_step(_agent: Agent, mut score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+        Box::pin(async move {
+            score.record("a");
+
+            BenchmarkStepResult::new(score, errors)
+        })
+    }
+
+    fn failure_step(_agent: Agent, mut score: Score, mut errors: Errors) -> BoxFutBenchmarkStep {
+        Box::pin(async move {
+            score.record("a");
+
+            errors.record(BenchmarkError::Fail {
+                cause: "error".into(),
+            });
+
+            BenchmarkStepResult::new(score, errors)
+        })
+    }
+
+    fn phase_benchmark(
+        prepare_step: BenchmarkStep,
+        load_step: BenchmarkStep,
+        validation_step: BenchmarkStep,
+    ) -> Benchmark {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+
+        let errors = Errors::new();
+
+        let parallels = 8;
+
+        let mut benchmark = Benchmark::new(agent, score, errors, parallels);
+
+        let mut prepare_scenario = BenchmarkScenario::new("prepare");
+        prepare_scenario.add_benchmark_step(prepare_step);
+        benchmark.add_prepare_scenario(prepare_scenario);
+
+        for _ in 0..2 {
+            let mut load_scenario = BenchmarkScenario::new("load");
+            load_scenario.add_benchmark_step(load_step);
+            benchmark.add_load_scenario(load_scenario);
+        }
+
+        let mut validation_scenario = BenchmarkScenario::new("validation");
+        validation_scenario.add_benchmark_step(validation_step);
+        benchmark.add_validation_scenario(validation_scenario);
+
+        benchmark
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_prepare_failure_skips_load() -> Result<(), ()> {
+        let benchmark = phase_benchmark(failure_step, success_step, success_step);
+
+        let benchmark_result = benchmark.start().await;
+        assert!(benchmark_result.is_failure());
+        assert_eq!(
+            benchmark_result.skipped_phases(),
+            vec![BenchmarkPhase::Load]
+        );
+
+        let phase_results = benchmark_result.phase_results();
+        assert_eq!(phase_results.len(), 3);
+        assert!(phase_results[0].fails_run());
+        assert!(phase_results[1].is_skipped());
+        assert!(phase_results[1].details().is_empty());
+        assert_eq!(phase_results[1].timing(), None);
+        assert!(phase_results[2].is_success());
+        assert_eq!(benchmark_result.details().len(), 2);
+
+        let unprocessed_details = benchmark_result.unprocessed_details();
+        assert_eq!(unprocessed_details.len(), 2);
+        assert!(unprocessed_details
+            .iter()
+            .all(|result| result.status() == ScenarioStatus::Skipped
+                && result.phase() == Some(BenchmarkPhase::Load)));
+        let scenario_counts = benchmark_result.scenario_counts();
+        assert_eq!(scenario_counts.skipped, 2);
+        assert!(scenario_counts.completion_ratio() < 1.0);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_phase_rules() -> Result<(), ()> {
+        let mut benchmark = phase_benchmark(success_step, failure_step, success_step);
+        benchmark.set_phase_rule(BenchmarkPhase::Load, PhaseRule::Penalize { point: 5 });
+
+        let benchmark_result = benchmark.start().await;
+        assert!(benchmark_result.is_success());
+
+        let load_result = benchmark_result.phase_result(BenchmarkPhase::Load).unwrap();
+        assert!(load_result.is_failure());
+        assert!(!load_result.fails_run());
+        assert_eq!(load_result.penalty_point(), 10);
+        assert_eq!(benchmark_result.total_gain(), 4);
+        assert_eq!(benchmark_result.total_lose(), 10);
+        assert_eq!(benchmark_result.total_score(), -6);
+
+        let mut benchmark = phase_benchmark(success_step, failure_step, failure_step);
+        benchmark.set_phase_rule(BenchmarkPhase::Load, PhaseRule::Penalize { point: 5 });
+
+        let benchmark_result = benchmark.start().await;
+        assert!(benchmark_result.is_failure());
+        assert!(benchmark_result
+            .phase_result(BenchmarkPhase::Validation)
+            .unwrap()
+            .fails_run());
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_custom_phases() -> Result<(), ()> {
+        let mut benchmark = phase_benchmark(success_step, success_step, success_step);
+
+        fn slow_step(_agent: Agent, mut score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                task::sleep(Duration::from_millis(70)).await;
+
+                score.record("a");
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut initialize_phase = CustomPhase::new("initialize");
+        let mut initialize_scenario = BenchmarkScenario::new("initialize");
+        initialize_scenario.add_benchmark_step(success_step);
+        initialize_phase.add_scenario(initialize_scenario);
+        initialize_phase.set_scored(false);
+
+        let mut consistency_phase = CustomPhase::new("consistency");
+        for _ in 0..3 {
+            let mut consistency_scenario = BenchmarkScenario::new("consistency");
+            consistency_scenario.add_benchmark_step(success_step);
+            consistency_phase.add_scenario(consistency_scenario);
+        }
+        consistency_phase.set_execution_mode(ExecutionMode::Parallel(2));
+
+        let mut cleanup_phase = CustomPhase::new("cleanup");
+        for _ in 0..3 {
+            let mut cleanup_scenario = BenchmarkScenario::new("cleanup");
+            cleanup_scenario.add_benchmark_step(slow_step);
+            cleanup_phase.add_scenario(cleanup_scenario);
+        }
+        cleanup_phase.set_execution_mode(ExecutionMode::TimeBoxed(Duration::from_millis(100)));
+
+        let initialize = initialize_phase.phase();
+        let consistency = consistency_phase.phase();
+        let cleanup = cleanup_phase.phase();
+        benchmark.add_custom_phase(initialize_phase);
+        benchmark.add_custom_phase(consistency_phase);
+        benchmark.add_custom_phase(cleanup_phase);
+        benchmark.set_phase_order(vec![
+            initialize.clone(),
+            BenchmarkPhase::Prepare,
+            BenchmarkPhase::Load,
+            consistency.clone(),
+            BenchmarkPhase::Validation,
+            cleanup.clone(),
+        ]);
+
+        let benchmark_result = benchmark.start().await;
+        assert!(benchmark_result.is_success());
+        assert_eq!(
+            benchmark_result
+                .phase_timings()
+                .iter()
+                .map(|phase_timing| phase_timing.phase.name())
+                .collect::<Vec<_>>(),
+            vec![
+                "initialize",
+                "prepare",
+                "load",
+                "consistency",
+                "validation",
+                "cleanup"
+            ]
+        );
+        assert_eq!(
+            benchmark_result
+                .phase_results()
+                .iter()
+                .map(|phase_result| phase_result.phase())
+                .collect::<Vec<_>>(),
+            vec![
+                initialize.clone(),
+                BenchmarkPhase::Prepare,
+                BenchmarkPhase::Load,
+                consistency.clone(),
+                BenchmarkPhase::Validation,
+                cleanup.clone(),
+            ]
+        );
+
+        let initialize_result = benchmark_result.phase_result(initialize).unwrap();
+        assert!(!initialize_result.is_scored());
+        assert_eq!(initialize_result.total_gain(), 1);
+
+        let consistency_result = benchmark_result.phase_result(consistency).unwrap();
+        assert_eq!(consistency_result.details().len(), 3);
+
+        let cleanup_result = benchmark_result.phase_result(cleanup).unwrap();
+        assert_eq!(cleanup_result.details().len(), 1);
+        assert_eq!(
+            benchmark_result
+                .unprocessed_details()
+                .iter()
+                .map(|result| result.status())
+                .collect::<Vec<_>>(),
+            vec![ScenarioStatus::TimedOut, ScenarioStatus::Canceled]
+        );
+
+        assert_eq!(benchmark_result.total_gain(), 8);
+        assert_eq!(benchmark_result.breakdown()["a"].count, 8);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_warm_up() -> Result<(), ()> {
+        fn penalty_step(
+            _agent: Agent,
+            mut score: Score,
+            mut errors: Errors,
+        ) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                score.record("a");
+
+                errors.record(BenchmarkError::Penalty {
+                    cause: "slow".into(),
+                    point: 1,
+                });
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark = phase_benchmark(success_step, penalty_step, success_step);
+        benchmark.set_warm_up(WarmUp::Count(4));
+
+        let benchmark_result = benchmark.start().await;
+        assert!(benchmark_result.is_success());
+        assert_eq!(
+            benchmark_result.phases(),
+            vec![
+                BenchmarkPhase::Prepare,
+                BenchmarkPhase::WarmUp,
+                BenchmarkPhase::Load,
+                BenchmarkPhase::Validation
+            ]
+        );
+
+        let warm_up_result = benchmark_result
+            .phase_result(BenchmarkPhase::WarmUp)
+            .unwrap();
+        assert!(!warm_up_result.is_scored());
+        assert_eq!(warm_up_result.details().len(), 4);
+        assert_eq!(warm_up_result.total_gain(), 0);
+        assert_eq!(warm_up_result.total_lose(), 0);
+        assert!(warm_up_result
+            .details()
+            .iter()
+            .all(|result| result.error_count() == 0));
+
+        assert_eq!(benchmark_result.total_gain(), 4);
+        assert_eq!(benchmark_result.total_lose(), 2);
+        assert_eq!(benchmark_result.error_records().len(), 2);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_warm_up_requests() -> Result<(), ()> {
+        let _m = mockito::mock("GET", "/warm_up_requests")
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        fn request_step(agent: Agent, mut score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                let response = agent.get("/warm_up_requests").await;
+                assert_eq!(response.unwrap().status(), surf::StatusCode::Ok);
+
+                score.record("a");
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        fn penalty_request_step(
+            agent: Agent,
+            mut score: Score,
+            mut errors: Errors,
+        ) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                let response = agent.get("/warm_up_requests").await;
+                assert_eq!(response.unwrap().status(), surf::StatusCode::Ok);
+
+                score.record("a");
+
+                errors.record(BenchmarkError::Penalty {
+                    cause: "slow".into(),
+                    point: 1,
+                });
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark = phase_benchmark(request_step, penalty_request_step, request_step);
+        benchmark.set_warm_up(WarmUp::Count(4));
+
+        let mut error_budget = ErrorBudget::new();
+        error_budget.set_max_error_ratio(0.5);
+        benchmark.set_error_budget(error_budget);
+
+        let benchmark_result = benchmark.start().await;
+        assert_eq!(benchmark_result.agent_metrics().requests, 8);
+        assert_eq!(benchmark_result.warm_up_requests(), 4);
+        assert_eq!(benchmark_result.scored_requests(), 4);
+        assert_eq!(benchmark_result.scoring_input().requests, 4);
+        assert_eq!(benchmark_result.endpoint_stats()[0].requests(), 4);
+        assert_eq!(
+            benchmark_result.budget_breaches(),
+            vec![BudgetBreach::ErrorRatio {
+                limit: 0.5,
+                actual: 1.0
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_unscored_phase_requests() -> Result<(), ()> {
+        let _m = mockito::mock("GET", "/unscored_requests")
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        fn request_step(agent: Agent, mut score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                let response = agent.get("/unscored_requests").await;
+                assert_eq!(response.unwrap().status(), surf::StatusCode::Ok);
+
+                score.record("a");
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        fn penalty_request_step(
+            agent: Agent,
+            mut score: Score,
+            mut errors: Errors,
+        ) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                let response = agent.get("/unscored_requests").await;
+                assert_eq!(response.unwrap().status(), surf::StatusCode::Ok);
+
+                score.record("a");
+
+                errors.record(BenchmarkError::Penalty {
+                    cause: "slow".into(),
+                    point: 1,
+                });
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark = phase_benchmark(request_step, request_step, request_step);
+
+        let mut smoke_phase = CustomPhase::new("smoke");
+        for _ in 0..2 {
+            let mut smoke_scenario = BenchmarkScenario::new("smoke");
+            smoke_scenario.add_benchmark_step(penalty_request_step);
+            smoke_phase.add_scenario(smoke_scenario);
+        }
+        smoke_phase.set_scored(false);
+        let smoke = smoke_phase.phase();
+        benchmark.add_custom_phase(smoke_phase);
+
+        let mut error_budget = ErrorBudget::new();
+        error_budget.set_max_penalty_point(0);
+        error_budget.set_max_error_ratio(0.0);
+        benchmark.set_error_budget(error_budget);
+
+        let benchmark_result = benchmark.start().await;
+        assert_eq!(benchmark_result.agent_metrics().requests, 6);
+        assert_eq!(benchmark_result.phase_requests(&smoke), 2);
+        assert_eq!(benchmark_result.scored_requests(), 4);
+        assert_eq!(benchmark_result.scoring_input().requests, 4);
+        assert_eq!(benchmark_result.error_groups().len(), 1);
+        assert!(benchmark_result.budget_breaches().is_empty());
+        assert_eq!(benchmark_result.total_lose(), 0);
+        assert!(benchmark_result.is_success());
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_warm_up_keeps_failures() -> Result<(), ()> {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        fn flaky_step(_agent: Agent, mut score: Score, mut errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                score.record("a");
+
+                if CALLS.fetch_add(1, Ordering::SeqCst) == 0 {
+                    errors.record(BenchmarkError::Fail {
+                        cause: "cold".into(),
+                    });
+                } else {
+                    errors.record(BenchmarkError::Penalty {
+                        cause: "slow".into(),
+                        point: 1,
+                    });
+                }
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark = phase_benchmark(success_step, flaky_step, success_step);
+        benchmark.set_warm_up(WarmUp::Duration(Duration::from_millis(50)));
+
+        let benchmark_result = benchmark.start().await;
+        assert!(benchmark_result.is_failure());
+        assert!(benchmark_result
+            .phase_result(BenchmarkPhase::WarmUp)
+            .unwrap()
+            .fails_run());
+        assert_eq!(
+            benchmark_result.skipped_phases(),
+            vec![BenchmarkPhase::Load]
+        );
+        assert_eq!(
+            benchmark_result
+                .error_records()
+                .iter()
+                .map(|record| record.code())
+                .collect::<Vec<_>>(),
+            vec!["cold"]
+        );
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_initialize() -> Result<(), ()> {
+        let path = "/benchmark_initialize";
+
+        let _m = mockito::mock("POST", path)
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        let mut benchmark = phase_benchmark(success_step, success_step, success_step);
+        benchmark.set_initialize(Initialize::new(path, Duration::from_secs(1)));
+
+        let benchmark_result = benchmark.start().await;
+        assert!(benchmark_result.is_success());
+        assert_eq!(benchmark_result.initialize_error(), None);
+        assert_eq!(
+            benchmark_result.phase_timings()[0].phase,
+            BenchmarkPhase::Initialize
+        );
+        assert_eq!(benchmark_result.details().len(), 4);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_initialize_failure() -> Result<(), ()> {
+        let path = "/benchmark_initialize_failure";
+
+        let _m = mockito::mock("POST", path)
+            .with_status(surf::StatusCode::ServiceUnavailable as usize)
+            .create();
+
+        let mut benchmark = phase_benchmark(success_step, success_step, success_step);
+        benchmark.set_initialize(Initialize::new(path, Duration::from_secs(1)));
+
+        let benchmark_result = benchmark.start().await;
+        assert!(benchmark_result.is_failure());
+        assert_eq!(
+            benchmark_result.initialize_error(),
+            Some(InitializeError::Status {
+                path: path.into(),
+                status: 503
+            })
+        );
+        assert_eq!(
+            benchmark_result.skipped_phases(),
+            vec![
+                BenchmarkPhase::Prepare,
+                BenchmarkPhase::Load,
+                BenchmarkPhase::Validation
+            ]
+        );
+        assert!(benchmark_result.details().is_empty());
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_readiness_not_ready() -> Result<(), ()> {
+        let path = "/benchmark_readiness";
+
+        let _m = mockito::mock("GET", path)
+            .with_status(surf::StatusCode::ServiceUnavailable as usize)
+            .create();
+
+        let mut benchmark = phase_benchmark(success_step, success_step, success_step);
+        let mut readiness = Readiness::new(path);
+        readiness.set_max_wait(Duration::from_millis(50));
+        readiness.set_interval(Duration::from_millis(10));
+        benchmark.set_readiness(readiness);
+
+        let benchmark_result = benchmark.start().await;
+        assert!(benchmark_result.is_failure());
+        assert!(benchmark_result
+            .readiness_error()
+            .unwrap()
+            .to_string()
+            .starts_with("target not ready"));
+        assert_eq!(
+            benchmark_result.skipped_phases(),
+            vec![
+                BenchmarkPhase::Prepare,
+                BenchmarkPhase::Load,
+                BenchmarkPhase::Validation
+            ]
+        );
+        assert!(benchmark_result.details().is_empty());
+        assert!(benchmark_result.error_records().is_empty());
+        assert_eq!(benchmark_result.agent_metrics().requests, 0);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,149 @@
+use crate::errors::*;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+#[derive(Clone, Error, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BudgetBreach {
+    #[error("penalty point {actual:?} exceeds budget {limit:?}")]
+    PenaltyPoint { limit: usize, actual: usize },
+    #[error("penalty count of {code:?} {actual:?} exceeds budget {limit:?}")]
+    PenaltyCount {
+        code: String,
+        limit: usize,
+        actual: usize,
+    },
+    #[error("error ratio {actual:.3} exceeds budget {limit:.3}")]
+    ErrorRatio { limit: f64, actual: f64 },
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ErrorBudget {
+    max_penalty_point: Option<usize>,
+    max_penalty_counts: BTreeMap<String, usize>,
+    max_error_ratio: Option<f64>,
+}
+
+impl ErrorBudget {
+    pub fn new() -> ErrorBudget {
+        ErrorBudget {
+            max_penalty_point: None,
+            max_penalty_counts: BTreeMap::new(),
+            max_error_ratio: None,
+        }
+    }
+
+    pub fn set_max_penalty_point(&mut self, max_penalty_point: usize) {
+        self.max_penalty_point = Some(max_penalty_point);
+    }
+
+    pub fn set_max_penalty_count(&mut self, code: impl Into<String>, max_penalty_count: usize) {
+        self.max_penalty_counts
+            .insert(code.into(), max_penalty_count);
+    }
+
+    pub fn set_max_error_ratio(&mut self, max_error_ratio: f64) {
+        self.max_error_ratio = Some(max_error_ratio);
+    }
+
+    pub fn check(&self, groups: &[ErrorGroup], requests: usize) -> Vec<BudgetBreach> {
+        let mut breaches = Vec::new();
+
+        let penalty_point = groups
+            .iter()
+            .fold(0, |total, group| total + group.penalty_point);
+        if let Some(limit) = self.max_penalty_point {
+            if penalty_point > limit {
+                breaches.push(BudgetBreach::PenaltyPoint {
+                    limit,
+                    actual: penalty_point,
+                });
+            }
+        }
+
+        for (code, limit) in &self.max_penalty_counts {
+            let actual = groups
+                .iter()
+                .filter(|group| group.category == "penalty" && group.code == *code)
+                .fold(0, |total, group| total + group.count);
+            if actual > *limit {
+                breaches.push(BudgetBreach::PenaltyCount {
+                    code: code.clone(),
+                    limit: *limit,
+                    actual,
+                });
+            }
+        }
+
+        if let Some(limit) = self.max_error_ratio {
+            let errors = groups.iter().fold(0, |total, group| total + group.count);
+            let successes = requests.saturating_sub(errors);
+            let actual = errors as f64 / successes.max(1) as f64;
+            if actual > limit {
+                breaches.push(BudgetBreach::ErrorRatio { limit, actual });
+            }
+        }
+
+        breaches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::benchmark::budget::*;
+
+    fn groups() -> Vec<ErrorGroup> {
+        let mut errors = Errors::new();
+        for _ in 0..3 {
+            errors.record(BenchmarkError::Penalty {
+                cause: "timeout".into(),
+                point: 2,
+            });
+        }
+        errors.record(BenchmarkError::Penalty {
+            cause: "not_found".into(),
+            point: 1,
+        });
+        errors.groups()
+    }
+
+    #[test]
+    fn test_check_within_budget() {
+        let mut budget = ErrorBudget::new();
+        budget.set_max_penalty_point(7);
+        budget.set_max_penalty_count("timeout", 3);
+        budget.set_max_error_ratio(0.5);
+
+        assert!(budget.check(&groups(), 12).is_empty());
+        assert!(ErrorBudget::new().check(&groups(), 0).is_empty());
+    }
+
+    #[test]
+    fn test_check_breaches() {
+        let mut budget = ErrorBudget::new();
+        budget.set_max_penalty_point(6);
+        budget.set_max_penalty_count("timeout", 2);
+        budget.set_max_penalty_count("not_found", 1);
+        budget.set_max_error_ratio(0.25);
+
+        assert_eq!(
+            budget.check(&groups(), 12),
+            vec![
+                BudgetBreach::PenaltyPoint {
+                    limit: 6,
+                    actual: 7
+                },
+                BudgetBreach::PenaltyCount {
+                    code: "timeout".into(),
+                    limit: 2,
+                    actual: 3
+                },
+                BudgetBreach::ErrorRatio {
+                    limit: 0.25,
+                    actual: 0.5
+                },
+            ]
+        );
+    }
+}
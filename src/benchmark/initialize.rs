@@ -0,0 +1,174 @@
+use crate::agent::*;
+
+use async_std::future;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Clone, Error, Debug, PartialEq, Serialize, Deserialize)]
+pub enum InitializeError {
+    #[error("initialize {path} did not respond within {deadline:?}")]
+    Timeout { path: String, deadline: Duration },
+    #[error("initialize {path} responded with status {status}")]
+    Status { path: String, status: u16 },
+    #[error("initialize {path} failed: {cause}")]
+    Request { path: String, cause: String },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Initialize {
+    path: String,
+    payload: String,
+    deadline: Duration,
+}
+
+impl Initialize {
+    pub fn new(path: impl Into<String>, deadline: Duration) -> Initialize {
+        Initialize {
+            path: path.into(),
+            payload: String::new(),
+            deadline,
+        }
+    }
+
+    pub fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    pub fn deadline(&self) -> Duration {
+        self.deadline
+    }
+
+    pub fn set_payload(&mut self, payload: impl Into<String>) {
+        self.payload = payload.into();
+    }
+
+    pub async fn run(&self, agent: &Agent) -> Result<(), InitializeError> {
+        let request = async {
+            let mut response = agent
+                .probe_post(self.path.clone(), self.payload.clone())
+                .await?;
+            response.body_bytes().await?;
+            Ok::<_, surf::Error>(response.status())
+        };
+
+        match future::timeout(self.deadline, request).await {
+            Err(_) => Err(InitializeError::Timeout {
+                path: self.path.clone(),
+                deadline: self.deadline,
+            }),
+            Ok(Err(error)) => Err(InitializeError::Request {
+                path: self.path.clone(),
+                cause: error.to_string(),
+            }),
+            Ok(Ok(status)) if !status.is_success() => Err(InitializeError::Status {
+                path: self.path.clone(),
+                status: status as u16,
+            }),
+            Ok(Ok(_)) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::agent::rate_limiter::*;
+    use crate::benchmark::initialize::*;
+    use mockito;
+
+    #[async_std::test]
+    async fn test_initialize() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+        let path = "/initialize";
+
+        let _m = mockito::mock("POST", path)
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        let agent = Agent::new(base_url);
+
+        let initialize = Initialize::new(path, Duration::from_secs(1));
+        assert_eq!(initialize.run(&agent).await, Ok(()));
+        assert_eq!(agent.metrics().requests, 0);
+        assert!(agent.endpoint_stats().is_empty());
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_initialize_not_rate_limited() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+        let path = "/initialize_not_rate_limited";
+
+        let _m = mockito::mock("POST", path)
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        let rate_limiter = RateLimiter::per_second(0.1).unwrap();
+        rate_limiter.acquire().await;
+
+        let mut agent = Agent::new(base_url);
+        agent.set_rate_limiter(rate_limiter);
+
+        let initialize = Initialize::new(path, Duration::from_secs(1));
+        assert_eq!(initialize.run(&agent).await, Ok(()));
+        assert_eq!(agent.metrics().rate_limit_wait, Duration::from_secs(0));
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_initialize_status() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+        let path = "/initialize_status";
+
+        let _m = mockito::mock("POST", path)
+            .with_status(surf::StatusCode::InternalServerError as usize)
+            .create();
+
+        let agent = Agent::new(base_url);
+
+        let initialize = Initialize::new(path, Duration::from_secs(1));
+        let error = initialize.run(&agent).await.unwrap_err();
+        assert_eq!(
+            error,
+            InitializeError::Status {
+                path: path.into(),
+                status: 500
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "initialize /initialize_status responded with status 500"
+        );
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_initialize_timeout() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+        let path = "/initialize_timeout";
+
+        let _m = mockito::mock("POST", path)
+            .with_status(surf::StatusCode::Ok as usize)
+            .with_body_from_fn(|_| {
+                std::thread::sleep(Duration::from_millis(200));
+                Ok(())
+            })
+            .create();
+
+        let agent = Agent::new(base_url);
+
+        let initialize = Initialize::new(path, Duration::from_millis(50));
+        assert_eq!(
+            initialize.run(&agent).await,
+            Err(InitializeError::Timeout {
+                path: path.into(),
+                deadline: Duration::from_millis(50)
+            })
+        );
+
+        Ok(())
+    }
+}
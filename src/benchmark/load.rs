@@ -0,0 +1,442 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use thiserror::Error;
+
+const DEFAULT_MAX_IN_FLIGHT: usize = 1024;
+
+#[derive(Clone, Default)]
+pub enum LoadModel {
+    #[default]
+    Closed,
+    Open(ArrivalRate),
+    Staged(LoadProfile),
+    Adaptive(AdaptiveConcurrency),
+}
+
+impl LoadModel {
+    pub fn deadline(&self) -> Option<Duration> {
+        match self {
+            LoadModel::Closed => None,
+            LoadModel::Open(arrival_rate) => Some(arrival_rate.total_duration()),
+            LoadModel::Staged(load_profile) => Some(load_profile.total_duration()),
+            LoadModel::Adaptive(adaptive) => Some(adaptive.duration()),
+        }
+    }
+}
+
+#[derive(Clone, Error, Debug, PartialEq)]
+pub enum LoadModelError {
+    #[error("arrival rate {rate:?} must be a finite number greater than zero")]
+    InvalidRate { rate: f64 },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrivalStage {
+    pub rate: f64,
+    pub duration: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrivalRate {
+    stages: Vec<ArrivalStage>,
+    max_in_flight: usize,
+}
+
+impl ArrivalRate {
+    pub fn new() -> ArrivalRate {
+        ArrivalRate {
+            stages: Vec::new(),
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+        }
+    }
+
+    pub fn constant(rate: f64, duration: Duration) -> Result<ArrivalRate, LoadModelError> {
+        let mut arrival_rate = ArrivalRate::new();
+        arrival_rate.add_stage(rate, duration)?;
+        Ok(arrival_rate)
+    }
+
+    pub fn add_stage(&mut self, rate: f64, duration: Duration) -> Result<(), LoadModelError> {
+        // the arrival interval must be representable as a Duration
+        if !rate.is_finite() || rate <= 0.0 || Duration::try_from_secs_f64(1.0 / rate).is_err() {
+            return Err(LoadModelError::InvalidRate { rate });
+        }
+
+        self.stages.push(ArrivalStage { rate, duration });
+        Ok(())
+    }
+
+    pub fn set_max_in_flight(&mut self, max_in_flight: usize) {
+        self.max_in_flight = max_in_flight.max(1);
+    }
+
+    pub fn stages(&self) -> Vec<ArrivalStage> {
+        self.stages.clone()
+    }
+
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight
+    }
+
+    pub fn total_duration(&self) -> Duration {
+        self.stages
+            .iter()
+            .fold(Duration::from_secs(0), |total, stage| {
+                total + stage.duration
+            })
+    }
+
+    pub fn total_arrivals(&self) -> usize {
+        self.stages.iter().fold(0, |total, stage| {
+            total + (stage.rate * stage.duration.as_secs_f64()).ceil() as usize
+        })
+    }
+}
+
+impl Default for ArrivalRate {
+    fn default() -> ArrivalRate {
+        ArrivalRate::new()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadStage {
+    pub parallels: usize,
+    pub duration: Duration,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LoadProfile {
+    stages: Vec<LoadStage>,
+}
+
+impl LoadProfile {
+    pub fn new() -> LoadProfile {
+        LoadProfile { stages: Vec::new() }
+    }
+
+    pub fn add_stage(&mut self, parallels: usize, duration: Duration) {
+        self.stages.push(LoadStage {
+            parallels: parallels.max(1),
+            duration,
+        });
+    }
+
+    pub fn stages(&self) -> Vec<LoadStage> {
+        self.stages.clone()
+    }
+
+    pub fn total_duration(&self) -> Duration {
+        self.stages
+            .iter()
+            .fold(Duration::from_secs(0), |total, stage| {
+                total + stage.duration
+            })
+    }
+
+    pub fn stage_at(&self, elapsed: Duration) -> Option<(usize, LoadStage)> {
+        let mut ended_at = Duration::from_secs(0);
+
+        for (index, stage) in self.stages.iter().enumerate() {
+            ended_at += stage.duration;
+            if elapsed < ended_at {
+                return Some((index, stage.clone()));
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdaptiveConcurrency {
+    duration: Duration,
+    initial_parallels: usize,
+    min_parallels: usize,
+    max_parallels: usize,
+    step: usize,
+    interval: Duration,
+    max_error_rate: f64,
+    max_latency: Duration,
+}
+
+impl AdaptiveConcurrency {
+    pub fn new(duration: Duration) -> AdaptiveConcurrency {
+        AdaptiveConcurrency {
+            duration,
+            initial_parallels: 1,
+            min_parallels: 1,
+            max_parallels: DEFAULT_MAX_IN_FLIGHT,
+            step: 1,
+            interval: Duration::from_secs(1),
+            max_error_rate: 0.0,
+            max_latency: Duration::from_secs(1),
+        }
+    }
+
+    pub fn set_parallels(&mut self, initial: usize, min: usize, max: usize) {
+        self.min_parallels = min.max(1);
+        self.max_parallels = max.max(self.min_parallels);
+        self.initial_parallels = initial.max(self.min_parallels).min(self.max_parallels);
+    }
+
+    pub fn set_step(&mut self, step: usize) {
+        self.step = step.max(1);
+    }
+
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    pub fn set_max_error_rate(&mut self, max_error_rate: f64) {
+        self.max_error_rate = max_error_rate;
+    }
+
+    pub fn set_max_latency(&mut self, max_latency: Duration) {
+        self.max_latency = max_latency;
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn initial_parallels(&self) -> usize {
+        self.initial_parallels
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TimelineEvent {
+    ConcurrencyAdjusted {
+        elapsed: Duration,
+        from: usize,
+        to: usize,
+        error_rate: f64,
+        latency: Duration,
+    },
+}
+
+pub(crate) struct AdaptiveController {
+    adaptive: AdaptiveConcurrency,
+    parallels: usize,
+    window_started_at: Duration,
+    completed: usize,
+    failed: usize,
+    latency: Duration,
+}
+
+impl AdaptiveController {
+    pub(crate) fn new(adaptive: AdaptiveConcurrency) -> AdaptiveController {
+        AdaptiveController {
+            parallels: adaptive.initial_parallels,
+            adaptive,
+            window_started_at: Duration::from_secs(0),
+            completed: 0,
+            failed: 0,
+            latency: Duration::from_secs(0),
+        }
+    }
+
+    pub(crate) fn parallels(&self) -> usize {
+        self.parallels
+    }
+
+    pub(crate) fn record(&mut self, latency: Duration, is_error: bool) {
+        self.completed += 1;
+        self.latency += latency;
+        if is_error {
+            self.failed += 1;
+        }
+    }
+
+    pub(crate) fn adjust(&mut self, elapsed: Duration) -> Option<TimelineEvent> {
+        if elapsed < self.window_started_at + self.adaptive.interval || self.completed == 0 {
+            return None;
+        }
+
+        let error_rate = self.failed as f64 / self.completed as f64;
+        let latency = self.latency / self.completed as u32;
+        let is_healthy =
+            error_rate <= self.adaptive.max_error_rate && latency <= self.adaptive.max_latency;
+
+        let from = self.parallels;
+        self.parallels = if is_healthy {
+            (self.parallels + self.adaptive.step).min(self.adaptive.max_parallels)
+        } else {
+            (self.parallels / 2).max(self.adaptive.min_parallels)
+        };
+
+        self.window_started_at = elapsed;
+        self.completed = 0;
+        self.failed = 0;
+        self.latency = Duration::from_secs(0);
+
+        if from == self.parallels {
+            return None;
+        }
+
+        Some(TimelineEvent::ConcurrencyAdjusted {
+            elapsed,
+            from,
+            to: self.parallels,
+            error_rate,
+            latency,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::benchmark::load::*;
+
+    #[test]
+    fn test_arrival_rate_constant() {
+        let arrival_rate = ArrivalRate::constant(10.0, Duration::from_secs(2)).unwrap();
+
+        assert_eq!(
+            arrival_rate.stages(),
+            vec![ArrivalStage {
+                rate: 10.0,
+                duration: Duration::from_secs(2)
+            }]
+        );
+        assert_eq!(arrival_rate.max_in_flight(), DEFAULT_MAX_IN_FLIGHT);
+        assert_eq!(arrival_rate.total_duration(), Duration::from_secs(2));
+        assert_eq!(arrival_rate.total_arrivals(), 20);
+    }
+
+    #[test]
+    fn test_arrival_rate_schedule() {
+        let mut arrival_rate = ArrivalRate::new();
+        arrival_rate
+            .add_stage(10.0, Duration::from_secs(1))
+            .unwrap();
+        arrival_rate
+            .add_stage(20.0, Duration::from_secs(3))
+            .unwrap();
+        arrival_rate.set_max_in_flight(8);
+
+        assert_eq!(arrival_rate.stages().len(), 2);
+        assert_eq!(arrival_rate.max_in_flight(), 8);
+        assert_eq!(arrival_rate.total_duration(), Duration::from_secs(4));
+        assert_eq!(arrival_rate.total_arrivals(), 70);
+    }
+
+    #[test]
+    fn test_arrival_rate_invalid_rate() {
+        let mut arrival_rate = ArrivalRate::new();
+        for rate in [0.0, -1.0, f64::NAN, f64::INFINITY, f64::MIN_POSITIVE].iter() {
+            assert!(matches!(
+                arrival_rate.add_stage(*rate, Duration::from_secs(1)),
+                Err(LoadModelError::InvalidRate { .. })
+            ));
+        }
+        assert_eq!(arrival_rate.stages(), Vec::new());
+    }
+
+    #[test]
+    fn test_load_profile() {
+        let mut load_profile = LoadProfile::new();
+        load_profile.add_stage(4, Duration::from_secs(10));
+        load_profile.add_stage(16, Duration::from_secs(30));
+        load_profile.add_stage(32, Duration::from_secs(20));
+
+        assert_eq!(load_profile.stages().len(), 3);
+        assert_eq!(load_profile.total_duration(), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_load_profile_stage_at() {
+        let mut load_profile = LoadProfile::new();
+        load_profile.add_stage(4, Duration::from_secs(10));
+        load_profile.add_stage(16, Duration::from_secs(30));
+
+        assert_eq!(
+            load_profile.stage_at(Duration::from_secs(0)),
+            Some((
+                0,
+                LoadStage {
+                    parallels: 4,
+                    duration: Duration::from_secs(10)
+                }
+            ))
+        );
+        assert_eq!(
+            load_profile
+                .stage_at(Duration::from_secs(10))
+                .map(|(index, _)| index),
+            Some(1)
+        );
+        assert_eq!(
+            load_profile
+                .stage_at(Duration::from_secs(39))
+                .map(|(index, _)| index),
+            Some(1)
+        );
+        assert_eq!(load_profile.stage_at(Duration::from_secs(40)), None);
+    }
+
+    #[test]
+    fn test_adaptive_concurrency_parallels() {
+        let mut adaptive = AdaptiveConcurrency::new(Duration::from_secs(60));
+        adaptive.set_parallels(0, 2, 8);
+
+        assert_eq!(adaptive.initial_parallels(), 2);
+        assert_eq!(adaptive.duration(), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_adaptive_controller_increase() {
+        let mut adaptive = AdaptiveConcurrency::new(Duration::from_secs(60));
+        adaptive.set_parallels(2, 1, 3);
+        adaptive.set_step(2);
+        adaptive.set_interval(Duration::from_secs(1));
+        adaptive.set_max_latency(Duration::from_millis(100));
+
+        let mut controller = AdaptiveController::new(adaptive);
+        controller.record(Duration::from_millis(50), false);
+
+        assert_eq!(controller.adjust(Duration::from_millis(500)), None);
+        assert_eq!(
+            controller.adjust(Duration::from_secs(1)),
+            Some(TimelineEvent::ConcurrencyAdjusted {
+                elapsed: Duration::from_secs(1),
+                from: 2,
+                to: 3,
+                error_rate: 0.0,
+                latency: Duration::from_millis(50),
+            })
+        );
+
+        controller.record(Duration::from_millis(50), false);
+        assert_eq!(controller.adjust(Duration::from_secs(2)), None);
+        assert_eq!(controller.parallels(), 3);
+    }
+
+    #[test]
+    fn test_adaptive_controller_decrease() {
+        let mut adaptive = AdaptiveConcurrency::new(Duration::from_secs(60));
+        adaptive.set_parallels(8, 3, 16);
+        adaptive.set_max_error_rate(0.1);
+        adaptive.set_max_latency(Duration::from_millis(100));
+
+        let mut controller = AdaptiveController::new(adaptive);
+        controller.record(Duration::from_millis(50), true);
+        controller.record(Duration::from_millis(50), false);
+
+        assert_eq!(
+            controller.adjust(Duration::from_secs(1)),
+            Some(TimelineEvent::ConcurrencyAdjusted {
+                elapsed: Duration::from_secs(1),
+                from: 8,
+                to: 4,
+                error_rate: 0.5,
+                latency: Duration::from_millis(50),
+            })
+        );
+
+        controller.record(Duration::from_millis(500), false);
+        controller.adjust(Duration::from_secs(2));
+        assert_eq!(controller.parallels(), 3);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::benchmark::scenario::*;
+use crate::benchmark::timing::*;
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExecutionMode {
+    #[default]
+    Sequential,
+    Parallel(usize),
+    TimeBoxed(Duration),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WarmUp {
+    Duration(Duration),
+    Count(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct CustomPhase {
+    name: String,
+    scenarios: Vec<BenchmarkScenario>,
+    execution_mode: ExecutionMode,
+    scored: bool,
+}
+
+impl CustomPhase {
+    pub fn new(name: impl Into<String>) -> CustomPhase {
+        CustomPhase {
+            name: name.into(),
+            scenarios: Vec::new(),
+            execution_mode: ExecutionMode::Sequential,
+            scored: true,
+        }
+    }
+
+    pub fn phase(&self) -> BenchmarkPhase {
+        BenchmarkPhase::Custom(self.name.clone())
+    }
+
+    pub fn add_scenario(&mut self, scenario: BenchmarkScenario) {
+        self.scenarios.push(scenario);
+    }
+
+    pub fn scenarios(&self) -> Vec<BenchmarkScenario> {
+        self.scenarios.clone()
+    }
+
+    pub fn execution_mode(&self) -> ExecutionMode {
+        self.execution_mode
+    }
+
+    pub fn set_execution_mode(&mut self, execution_mode: ExecutionMode) {
+        self.execution_mode = execution_mode;
+    }
+
+    pub fn is_scored(&self) -> bool {
+        self.scored
+    }
+
+    pub fn set_scored(&mut self, scored: bool) {
+        self.scored = scored;
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PhaseRule {
+    #[default]
+    FailRun,
+    Penalize {
+        point: usize,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PhaseResult {
+    phase: BenchmarkPhase,
+    rule: PhaseRule,
+    scored: bool,
+    skipped: bool,
+    timing: Option<Timing>,
+    scenario_results: Vec<BenchmarkScenarioResult>,
+}
+
+impl PhaseResult {
+    pub fn new(phase: BenchmarkPhase, rule: PhaseRule) -> PhaseResult {
+        PhaseResult {
+            phase,
+            rule,
+            scored: true,
+            skipped: false,
+            timing: None,
+            scenario_results: Vec::new(),
+        }
+    }
+
+    pub fn phase(&self) -> BenchmarkPhase {
+        self.phase.clone()
+    }
+
+    pub fn rule(&self) -> PhaseRule {
+        self.rule
+    }
+
+    pub fn is_scored(&self) -> bool {
+        self.scored
+    }
+
+    pub fn set_scored(&mut self, scored: bool) {
+        self.scored = scored;
+    }
+
+    pub fn is_skipped(&self) -> bool {
+        self.skipped
+    }
+
+    pub fn set_skipped(&mut self, skipped: bool) {
+        self.skipped = skipped;
+    }
+
+    pub fn timing(&self) -> Option<Timing> {
+        self.timing
+    }
+
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.timing = Some(timing);
+    }
+
+    pub fn details(&self) -> Vec<BenchmarkScenarioResult> {
+        self.scenario_results.clone()
+    }
+
+    pub fn add_scenario_result(&mut self, result: BenchmarkScenarioResult) {
+        self.scenario_results.push(result);
+    }
+
+    pub fn failed_scenarios(&self) -> usize {
+        self.scenario_results
+            .iter()
+            .filter(|result| result.is_failure())
+            .count()
+    }
+
+    pub fn penalty_point(&self) -> usize {
+        match self.rule {
+            PhaseRule::FailRun => 0,
+            PhaseRule::Penalize { point } => point * self.failed_scenarios(),
+        }
+    }
+
+    pub fn total_score(&self) -> isize {
+        self.total_gain() - self.total_lose()
+    }
+
+    pub fn total_gain(&self) -> isize {
+        self.scenario_results
+            .iter()
+            .fold(0, |total, result| total + result.total_gain())
+    }
+
+    pub fn total_lose(&self) -> isize {
+        self.scenario_results
+            .iter()
+            .fold(self.penalty_point() as isize, |total, result| {
+                total + result.total_lose()
+            })
+    }
+
+    pub fn is_success(&self) -> bool {
+        !self.is_failure()
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.failed_scenarios() > 0
+    }
+
+    pub fn fails_run(&self) -> bool {
+        self.rule == PhaseRule::FailRun && self.is_failure()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::benchmark::phase::*;
+    use crate::benchmark::step::*;
+    use crate::errors::*;
+    use crate::score::*;
+
+    fn scenario_result(error: Option<BenchmarkError>) -> BenchmarkScenarioResult {
+        let mut score = Score::new();
+        score.add_point_table("a", 3);
+        score.record("a");
+
+        let mut errors = Errors::new();
+        if let Some(error) = error {
+            errors.record(error);
+        }
+
+        let mut result = BenchmarkScenarioResult::new("scenario");
+        result.set_phase(BenchmarkPhase::Load);
+        result.add_step_result(BenchmarkStepResult::new(score, errors));
+        result
+    }
+
+    fn phase_result(rule: PhaseRule) -> PhaseResult {
+        let mut phase_result = PhaseResult::new(BenchmarkPhase::Load, rule);
+        phase_result.add_scenario_result(scenario_result(None));
+        phase_result.add_scenario_result(scenario_result(Some(BenchmarkError::Penalty {
+            cause: "slow".into(),
+            point: 1,
+        })));
+        phase_result.add_scenario_result(scenario_result(Some(BenchmarkError::Fail {
+            cause: "broken".into(),
+        })));
+        phase_result
+    }
+
+    #[test]
+    fn test_phase_result_fail_run() {
+        let phase_result = phase_result(PhaseRule::FailRun);
+
+        assert!(phase_result.is_failure());
+        assert!(phase_result.fails_run());
+        assert_eq!(phase_result.failed_scenarios(), 1);
+        assert_eq!(phase_result.penalty_point(), 0);
+        assert_eq!(phase_result.total_score(), 8);
+    }
+
+    #[test]
+    fn test_phase_result_penalize() {
+        let phase_result = phase_result(PhaseRule::Penalize { point: 5 });
+
+        assert!(phase_result.is_failure());
+        assert!(!phase_result.fails_run());
+        assert_eq!(phase_result.penalty_point(), 5);
+        assert_eq!(phase_result.total_gain(), 9);
+        assert_eq!(phase_result.total_lose(), 6);
+        assert_eq!(phase_result.total_score(), 3);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::agent::*;
+
+use async_std::future;
+use async_std::task;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+#[derive(Clone, Error, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ReadinessError {
+    #[error("target not ready: {path} did not return status {expected_status} within {max_wait:?} after {attempts} attempts ({last})")]
+    NotReady {
+        path: String,
+        expected_status: u16,
+        max_wait: Duration,
+        attempts: usize,
+        last: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Readiness {
+    path: String,
+    expected_status: u16,
+    max_wait: Duration,
+    interval: Duration,
+}
+
+impl Readiness {
+    pub fn new(path: impl Into<String>) -> Readiness {
+        Readiness {
+            path: path.into(),
+            expected_status: 200,
+            max_wait: Duration::from_secs(30),
+            interval: Duration::from_secs(1),
+        }
+    }
+
+    pub fn set_expected_status(&mut self, expected_status: u16) {
+        self.expected_status = expected_status;
+    }
+
+    pub fn set_max_wait(&mut self, max_wait: Duration) {
+        self.max_wait = max_wait;
+    }
+
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    pub async fn wait(&self, agent: &Agent) -> Result<usize, ReadinessError> {
+        let started_at = Instant::now();
+        let mut attempts = 0;
+        let mut last = None;
+
+        loop {
+            attempts += 1;
+
+            let remaining = self.max_wait.saturating_sub(started_at.elapsed());
+            match future::timeout(remaining, agent.probe(self.path.clone())).await {
+                Ok(Ok(response)) if response.status() as u16 == self.expected_status => {
+                    return Ok(attempts);
+                }
+                Ok(Ok(response)) => last = Some(format!("status {}", response.status() as u16)),
+                Ok(Err(error)) => last = Some(error.to_string()),
+                Err(_) => {}
+            }
+            log::debug!("[Readiness] {} not ready: {:?}", self.path, last);
+
+            let remaining = self.max_wait.saturating_sub(started_at.elapsed());
+            if remaining.is_zero() {
+                return Err(ReadinessError::NotReady {
+                    path: self.path.clone(),
+                    expected_status: self.expected_status,
+                    max_wait: self.max_wait,
+                    attempts,
+                    last: last.unwrap_or_else(|| "timed out".into()),
+                });
+            }
+            task::sleep(self.interval.min(remaining)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::benchmark::readiness::*;
+    use mockito;
+
+    #[async_std::test]
+    async fn test_readiness() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+        let path = "/health";
+
+        let _m = mockito::mock("GET", path)
+            .with_status(surf::StatusCode::Ok as usize)
+            .create();
+
+        let agent = Agent::new(base_url);
+
+        let readiness = Readiness::new(path);
+        assert_eq!(readiness.wait(&agent).await, Ok(1));
+        assert_eq!(agent.metrics().requests, 0);
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_readiness_not_ready() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+        let path = "/health_not_ready";
+
+        let _m = mockito::mock("GET", path)
+            .with_status(surf::StatusCode::ServiceUnavailable as usize)
+            .create();
+
+        let agent = Agent::new(base_url);
+
+        let mut readiness = Readiness::new(path);
+        readiness.set_max_wait(Duration::from_millis(100));
+        readiness.set_interval(Duration::from_millis(20));
+
+        match readiness.wait(&agent).await {
+            Err(ReadinessError::NotReady {
+                expected_status,
+                attempts,
+                last,
+                ..
+            }) => {
+                assert_eq!(expected_status, 200);
+                assert!(attempts > 1);
+                assert_eq!(last, "status 503");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_readiness_expected_status() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+        let path = "/health_no_content";
+
+        let _m = mockito::mock("GET", path)
+            .with_status(surf::StatusCode::NoContent as usize)
+            .create();
+
+        let agent = Agent::new(base_url);
+
+        let mut readiness = Readiness::new(path);
+        readiness.set_expected_status(204);
+        assert_eq!(readiness.wait(&agent).await, Ok(1));
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_readiness_connection_refused() -> Result<(), ()> {
+        let agent = Agent::new("http://127.0.0.1:1");
+
+        let mut readiness = Readiness::new("/health");
+        readiness.set_max_wait(Duration::from_millis(50));
+        readiness.set_interval(Duration::from_millis(10));
+
+        let error = readiness.wait(&agent).await.unwrap_err();
+        assert!(error.to_string().starts_with("target not ready: /health"));
+
+        Ok(())
+    }
+}
@@ -1,12 +1,16 @@
 use crate::agent::*;
 use crate::benchmark::step::*;
+use crate::benchmark::timing::*;
 use crate::errors::*;
 use crate::score::*;
 
-#[derive(Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug)]
 pub struct BenchmarkScenario {
     pub name: String,
-    steps: Vec<BenchmarkStep>,
+    steps: Vec<(String, BenchmarkStep)>,
+    follow_up_scenarios: Vec<BenchmarkScenario>,
 }
 
 impl BenchmarkScenario {
@@ -14,29 +18,131 @@ impl BenchmarkScenario {
         BenchmarkScenario {
             name: name.into(),
             steps: Vec::new(),
+            follow_up_scenarios: Vec::new(),
         }
     }
 
     pub fn add_benchmark_step(&mut self, step: BenchmarkStep) {
-        self.steps.push(step);
+        let step_name = format!("step{}", self.steps.len());
+        self.steps.push((step_name, step));
     }
 
-    pub async fn run(self, agent: Agent, score: Score, errors: Errors) -> BenchmarkScenarioResult {
-        let mut scenario_result = BenchmarkScenarioResult::new(self.name);
+    pub fn add_named_benchmark_step(&mut self, step_name: impl Into<String>, step: BenchmarkStep) {
+        self.steps.push((step_name.into(), step));
+    }
+
+    pub fn step_names(&self) -> Vec<String> {
+        self.steps
+            .iter()
+            .map(|(step_name, _)| step_name.clone())
+            .collect()
+    }
 
-        for step in self.steps {
-            let result = step(agent.clone(), score.clone(), errors.clone()).await;
+    pub fn add_follow_up_scenario(&mut self, scenario: BenchmarkScenario) {
+        self.follow_up_scenarios.push(scenario);
+    }
+
+    pub async fn run(self, agent: Agent, score: Score, errors: Errors) -> BenchmarkScenarioResult {
+        let mut scenario_result = BenchmarkScenarioResult::new(&self.name);
+        let started_at = agent.elapsed();
+
+        for (index, (step_name, step)) in self.steps.into_iter().enumerate() {
+            let step_started_at = agent.elapsed();
+            let mut result = step(agent.clone(), score.clone(), errors.clone()).await;
+            result.set_step_name(step_name);
+            result.set_timing(Timing::new(step_started_at, agent.elapsed()));
+            result.set_error_origin(&self.name, index);
             scenario_result.add_step_result(result);
         }
 
+        scenario_result.set_timing(Timing::new(started_at, agent.elapsed()));
+
+        if scenario_result.is_success() {
+            for scenario in self.follow_up_scenarios {
+                scenario_result.add_follow_up_scenario(scenario);
+            }
+        }
+
         scenario_result
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BenchmarkPhase {
+    Initialize,
+    Prepare,
+    Load,
+    Validation,
+    WarmUp,
+    Custom(String),
+}
+
+impl BenchmarkPhase {
+    pub fn name(&self) -> String {
+        match self {
+            BenchmarkPhase::Initialize => "initialize".into(),
+            BenchmarkPhase::Prepare => "prepare".into(),
+            BenchmarkPhase::Load => "load".into(),
+            BenchmarkPhase::Validation => "validation".into(),
+            BenchmarkPhase::WarmUp => "warm_up".into(),
+            BenchmarkPhase::Custom(name) => name.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ScenarioStatus {
+    #[default]
+    Processed,
+    Canceled,
+    TimedOut,
+    Skipped,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioCounts {
+    pub processed: usize,
+    pub canceled: usize,
+    pub timed_out: usize,
+    pub skipped: usize,
+}
+
+impl ScenarioCounts {
+    pub fn new() -> ScenarioCounts {
+        ScenarioCounts::default()
+    }
+
+    pub fn add(&mut self, status: ScenarioStatus) {
+        match status {
+            ScenarioStatus::Processed => self.processed += 1,
+            ScenarioStatus::Canceled => self.canceled += 1,
+            ScenarioStatus::TimedOut => self.timed_out += 1,
+            ScenarioStatus::Skipped => self.skipped += 1,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.processed + self.canceled + self.timed_out + self.skipped
+    }
+
+    pub fn completion_ratio(&self) -> f64 {
+        match self.total() {
+            0 => 1.0,
+            total => self.processed as f64 / total as f64,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BenchmarkScenarioResult {
     pub scenario_name: String,
     step_results: Vec<BenchmarkStepResult>,
+    phase: Option<BenchmarkPhase>,
+    stage: Option<usize>,
+    status: ScenarioStatus,
+    timing: Timing,
+    #[serde(skip)]
+    follow_up_scenarios: Vec<BenchmarkScenario>,
 }
 
 impl BenchmarkScenarioResult {
@@ -44,6 +150,11 @@ impl BenchmarkScenarioResult {
         BenchmarkScenarioResult {
             scenario_name: scenario_name.into(),
             step_results: Vec::new(),
+            phase: None,
+            stage: None,
+            status: ScenarioStatus::Processed,
+            timing: Timing::default(),
+            follow_up_scenarios: Vec::new(),
         }
     }
 
@@ -51,10 +162,81 @@ impl BenchmarkScenarioResult {
         self.scenario_name.clone()
     }
 
-    pub fn add_step_result(&mut self, result: BenchmarkStepResult) {
+    pub fn phase(&self) -> Option<BenchmarkPhase> {
+        self.phase.clone()
+    }
+
+    pub fn set_phase(&mut self, phase: BenchmarkPhase) {
+        self.phase = Some(phase);
+    }
+
+    pub fn stage(&self) -> Option<usize> {
+        self.stage
+    }
+
+    pub fn set_stage(&mut self, stage: usize) {
+        self.stage = Some(stage);
+    }
+
+    pub fn status(&self) -> ScenarioStatus {
+        self.status
+    }
+
+    pub fn set_status(&mut self, status: ScenarioStatus) {
+        self.status = status;
+    }
+
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+    }
+
+    pub fn step_results(&self) -> Vec<BenchmarkStepResult> {
+        self.step_results.clone()
+    }
+
+    pub fn step_result(&self, step_name: &str) -> Option<BenchmarkStepResult> {
+        self.step_results
+            .iter()
+            .find(|result| result.step_name() == step_name)
+            .cloned()
+    }
+
+    pub fn failed_steps(&self) -> Vec<String> {
+        self.step_results
+            .iter()
+            .filter(|result| result.is_failure())
+            .map(|result| format!("{}/{}", self.scenario_name, result.step_name()))
+            .collect()
+    }
+
+    pub fn add_step_result(&mut self, mut result: BenchmarkStepResult) {
+        self.follow_up_scenarios
+            .append(&mut result.take_follow_up_scenarios());
         self.step_results.push(result);
     }
 
+    pub fn follow_up_scenarios(&self) -> Vec<BenchmarkScenario> {
+        self.follow_up_scenarios.clone()
+    }
+
+    pub fn add_follow_up_scenario(&mut self, scenario: BenchmarkScenario) {
+        self.follow_up_scenarios.push(scenario);
+    }
+
+    pub(crate) fn take_follow_up_scenarios(&mut self) -> Vec<BenchmarkScenario> {
+        std::mem::take(&mut self.follow_up_scenarios)
+    }
+
+    pub(crate) fn discard_except_failures(&mut self) {
+        for result in self.step_results.iter_mut() {
+            result.discard_except_failures();
+        }
+    }
+
     pub fn total_score(&self) -> isize {
         self.total_gain() - self.total_lose()
     }
@@ -71,6 +253,27 @@ impl BenchmarkScenarioResult {
             .fold(0, |total, result| total + result.total_lose())
     }
 
+    pub fn error_count(&self) -> usize {
+        self.step_results
+            .iter()
+            .fold(0, |total, result| total + result.error_count())
+    }
+
+    pub fn error_records(&self) -> Vec<ErrorRecord> {
+        self.step_results
+            .iter()
+            .flat_map(|result| result.error_records())
+            .collect()
+    }
+
+    pub fn error_groups(&self) -> Vec<ErrorGroup> {
+        group_error_records(self.error_records().iter())
+    }
+
+    pub fn breakdown(&self) -> ScoreBreakdown {
+        merge_breakdowns(self.step_results.iter().map(|result| result.breakdown()))
+    }
+
     pub fn is_success(&self) -> bool {
         !self.is_failure()
     }
@@ -78,12 +281,32 @@ impl BenchmarkScenarioResult {
     pub fn is_failure(&self) -> bool {
         self.step_results.iter().any(|result| result.is_failure())
     }
+
+    pub fn configuration_errors(&self) -> Vec<ScoreError> {
+        merge_configuration_errors(
+            self.step_results
+                .iter()
+                .flat_map(|result| result.configuration_errors()),
+        )
+    }
+}
+
+impl PartialEq for BenchmarkScenarioResult {
+    fn eq(&self, other: &Self) -> bool {
+        self.scenario_name == other.scenario_name
+            && self.step_results == other.step_results
+            && self.phase == other.phase
+            && self.stage == other.stage
+            && self.status == other.status
+            && self.timing == other.timing
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::benchmark::scenario::*;
     use mockito;
+    use std::time::Duration;
 
     #[async_std::test]
     async fn test_benchmark_scenario() -> Result<(), ()> {
@@ -162,9 +385,143 @@ mod tests {
         assert_eq!(benchmark_scenario_result.total_gain(), 6);
         assert_eq!(benchmark_scenario_result.total_lose(), 6);
 
+        let error_records = benchmark_scenario_result.error_records();
+        assert_eq!(error_records.len(), 3);
+        assert_eq!(error_records[1].context.scenario, Some("scenario".into()));
+        assert_eq!(error_records[1].context.step, Some(1));
+
+        let breakdown = benchmark_scenario_result.breakdown();
+        assert_eq!(breakdown.len(), 3);
+        assert_eq!(
+            breakdown["b"],
+            PointBreakdown {
+                count: 1,
+                subtotal: 2
+            }
+        );
+
         Ok(())
     }
 
+    #[async_std::test]
+    async fn test_benchmark_scenario_named_steps() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+
+        let errors = Errors::new();
+
+        let mut benchmark_scenario = BenchmarkScenario::new("checkout");
+
+        fn add_to_cart(_agent: Agent, mut score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                score.record("a");
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        fn confirm_payment(_agent: Agent, score: Score, mut errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                errors.record(BenchmarkError::Fail {
+                    cause: "payment declined".into(),
+                });
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        benchmark_scenario.add_named_benchmark_step("add_to_cart", add_to_cart);
+        benchmark_scenario.add_named_benchmark_step("confirm_payment", confirm_payment);
+        benchmark_scenario.add_benchmark_step(add_to_cart);
+        assert_eq!(
+            benchmark_scenario.step_names(),
+            vec!["add_to_cart", "confirm_payment", "step2"]
+        );
+
+        let benchmark_scenario_result = benchmark_scenario.run(agent, score, errors).await;
+        assert!(benchmark_scenario_result.is_failure());
+        assert_eq!(
+            benchmark_scenario_result.failed_steps(),
+            vec!["checkout/confirm_payment"]
+        );
+
+        let add_to_cart_result = benchmark_scenario_result
+            .step_result("add_to_cart")
+            .unwrap();
+        assert!(add_to_cart_result.is_success());
+        assert_eq!(add_to_cart_result.score().count("a"), 1);
+
+        let confirm_payment_result = benchmark_scenario_result
+            .step_result("confirm_payment")
+            .unwrap();
+        assert!(confirm_payment_result.is_failure());
+        assert_eq!(confirm_payment_result.errors().iter().count(), 1);
+        assert_eq!(
+            confirm_payment_result.error_records()[0].context.step_name,
+            Some("confirm_payment".into())
+        );
+
+        assert!(benchmark_scenario_result.step_result("missing").is_none());
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_scenario_timing() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let score = Score::new();
+
+        let errors = Errors::new();
+
+        let mut benchmark_scenario = BenchmarkScenario::new("scenario");
+
+        fn step(_agent: Agent, score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                async_std::task::sleep(Duration::from_millis(20)).await;
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        benchmark_scenario.add_benchmark_step(step);
+        benchmark_scenario.add_benchmark_step(step);
+
+        let benchmark_scenario_result = benchmark_scenario.run(agent, score, errors).await;
+        let step_results = benchmark_scenario_result.step_results();
+        let timing = benchmark_scenario_result.timing();
+
+        assert!(step_results[0].timing().duration() >= Duration::from_millis(20));
+        assert!(step_results[1].timing().duration() >= Duration::from_millis(20));
+        assert!(step_results[1].timing().started_at >= step_results[0].timing().ended_at);
+        assert!(timing.started_at <= step_results[0].timing().started_at);
+        assert!(timing.ended_at >= step_results[1].timing().ended_at);
+        assert!(timing.duration() >= Duration::from_millis(40));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scenario_counts() {
+        let mut counts = ScenarioCounts::new();
+        assert_eq!(counts.completion_ratio(), 1.0);
+
+        counts.add(ScenarioStatus::Processed);
+        counts.add(ScenarioStatus::Processed);
+        counts.add(ScenarioStatus::Processed);
+        counts.add(ScenarioStatus::Canceled);
+        counts.add(ScenarioStatus::TimedOut);
+        counts.add(ScenarioStatus::Skipped);
+        assert_eq!(counts.total(), 6);
+        assert_eq!(counts.completion_ratio(), 0.5);
+    }
+
     #[async_std::test]
     async fn test_benchmark_scenario_result_is_success() -> Result<(), ()> {
         let base_url = &mockito::server_url();
@@ -249,4 +606,71 @@ mod tests {
 
         Ok(())
     }
+
+    #[async_std::test]
+    async fn test_benchmark_scenario_result_follow_up_scenarios() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let score = Score::new();
+
+        let errors = Errors::new();
+
+        fn step(_agent: Agent, score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                let mut result = BenchmarkStepResult::new(score, errors);
+                result.add_follow_up_scenario(BenchmarkScenario::new("step_follow_up"));
+                result
+            })
+        }
+
+        let mut benchmark_scenario = BenchmarkScenario::new("scenario");
+        benchmark_scenario.add_benchmark_step(step);
+        benchmark_scenario.add_follow_up_scenario(BenchmarkScenario::new("scenario_follow_up"));
+
+        let benchmark_scenario_result = benchmark_scenario.run(agent, score, errors).await;
+        let follow_up_names: Vec<_> = benchmark_scenario_result
+            .follow_up_scenarios()
+            .into_iter()
+            .map(|scenario| scenario.name)
+            .collect();
+
+        assert_eq!(
+            follow_up_names,
+            vec!["step_follow_up", "scenario_follow_up"]
+        );
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_scenario_result_follow_up_scenarios_on_failure() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+
+        let agent = Agent::new(base_url);
+
+        let score = Score::new();
+
+        let errors = Errors::new();
+
+        fn step(_agent: Agent, score: Score, mut errors: Errors) -> BoxFutBenchmarkStep {
+            Box::pin(async move {
+                errors.record(BenchmarkError::Fail {
+                    cause: "error".into(),
+                });
+
+                BenchmarkStepResult::new(score, errors)
+            })
+        }
+
+        let mut benchmark_scenario = BenchmarkScenario::new("scenario");
+        benchmark_scenario.add_benchmark_step(step);
+        benchmark_scenario.add_follow_up_scenario(BenchmarkScenario::new("scenario_follow_up"));
+
+        let benchmark_scenario_result = benchmark_scenario.run(agent, score, errors).await;
+        assert!(benchmark_scenario_result.follow_up_scenarios().is_empty());
+
+        Ok(())
+    }
 }
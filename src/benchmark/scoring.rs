@@ -0,0 +1,250 @@
+use crate::benchmark::scenario::*;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PhaseCounts {
+    pub phase: BenchmarkPhase,
+    pub gain: isize,
+    pub lose: isize,
+    pub errors: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoringInput {
+    pub phases: Vec<PhaseCounts>,
+    pub requests: usize,
+}
+
+impl ScoringInput {
+    pub fn new(requests: usize) -> ScoringInput {
+        ScoringInput {
+            phases: Vec::new(),
+            requests,
+        }
+    }
+
+    pub fn add_scenario_result(&mut self, result: &BenchmarkScenarioResult) {
+        let phase = result.phase().unwrap_or(BenchmarkPhase::Load);
+
+        match self.phases.iter_mut().find(|counts| counts.phase == phase) {
+            Some(counts) => {
+                counts.gain += result.total_gain();
+                counts.lose += result.total_lose();
+                counts.errors += result.error_count();
+            }
+            None => self.phases.push(PhaseCounts {
+                phase,
+                gain: result.total_gain(),
+                lose: result.total_lose(),
+                errors: result.error_count(),
+            }),
+        }
+    }
+
+    pub fn add_phase_penalty(&mut self, phase: BenchmarkPhase, point: usize) {
+        match self.phases.iter_mut().find(|counts| counts.phase == phase) {
+            Some(counts) => counts.lose += point as isize,
+            None => self.phases.push(PhaseCounts {
+                phase,
+                gain: 0,
+                lose: point as isize,
+                errors: 0,
+            }),
+        }
+    }
+
+    pub fn total_gain(&self) -> isize {
+        self.phases
+            .iter()
+            .fold(0, |total, counts| total + counts.gain)
+    }
+
+    pub fn total_lose(&self) -> isize {
+        self.phases
+            .iter()
+            .fold(0, |total, counts| total + counts.lose)
+    }
+
+    pub fn total_errors(&self) -> usize {
+        self.phases
+            .iter()
+            .fold(0, |total, counts| total + counts.errors)
+    }
+
+    pub fn error_rate(&self) -> f64 {
+        self.total_errors() as f64 / self.requests.max(1) as f64
+    }
+}
+
+pub trait ScoringPolicy: Send + Sync {
+    fn score(&self, input: &ScoringInput) -> isize;
+}
+
+impl<F> ScoringPolicy for F
+where
+    F: Fn(&ScoringInput) -> isize + Send + Sync,
+{
+    fn score(&self, input: &ScoringInput) -> isize {
+        self(input)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Subtraction;
+
+impl ScoringPolicy for Subtraction {
+    fn score(&self, input: &ScoringInput) -> isize {
+        input.total_gain() - input.total_lose()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ClampAtZero<P: ScoringPolicy>(pub P);
+
+impl<P: ScoringPolicy> ScoringPolicy for ClampAtZero<P> {
+    fn score(&self, input: &ScoringInput) -> isize {
+        self.0.score(input).max(0)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PhaseMultipliers {
+    multipliers: HashMap<BenchmarkPhase, f64>,
+}
+
+impl PhaseMultipliers {
+    pub fn new() -> PhaseMultipliers {
+        PhaseMultipliers {
+            multipliers: HashMap::new(),
+        }
+    }
+
+    pub fn set_multiplier(&mut self, phase: BenchmarkPhase, multiplier: f64) {
+        self.multipliers.insert(phase, multiplier);
+    }
+}
+
+impl ScoringPolicy for PhaseMultipliers {
+    fn score(&self, input: &ScoringInput) -> isize {
+        input.phases.iter().fold(0, |total, counts| {
+            let multiplier = self.multipliers.get(&counts.phase).copied().unwrap_or(1.0);
+            total + ((counts.gain - counts.lose) as f64 * multiplier).round() as isize
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PenaltyPercentage {
+    pub percent_per_point: f64,
+}
+
+impl ScoringPolicy for PenaltyPercentage {
+    fn score(&self, input: &ScoringInput) -> isize {
+        let percent = (input.total_lose() as f64 * self.percent_per_point).clamp(0.0, 100.0);
+        (input.total_gain() as f64 * (100.0 - percent) / 100.0).round() as isize
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LowErrorBonus<P: ScoringPolicy> {
+    pub policy: P,
+    pub max_error_rate: f64,
+    pub bonus: isize,
+}
+
+impl<P: ScoringPolicy> ScoringPolicy for LowErrorBonus<P> {
+    fn score(&self, input: &ScoringInput) -> isize {
+        let score = self.policy.score(input);
+        if input.error_rate() <= self.max_error_rate {
+            score + self.bonus
+        } else {
+            score
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::benchmark::scoring::*;
+
+    fn input() -> ScoringInput {
+        ScoringInput {
+            phases: vec![
+                PhaseCounts {
+                    phase: BenchmarkPhase::Prepare,
+                    gain: 10,
+                    lose: 0,
+                    errors: 0,
+                },
+                PhaseCounts {
+                    phase: BenchmarkPhase::Load,
+                    gain: 100,
+                    lose: 20,
+                    errors: 4,
+                },
+            ],
+            requests: 200,
+        }
+    }
+
+    #[test]
+    fn test_subtraction() {
+        assert_eq!(Subtraction.score(&input()), 90);
+    }
+
+    #[test]
+    fn test_clamp_at_zero() {
+        let mut input = input();
+        input.phases[1].lose = 500;
+
+        assert_eq!(Subtraction.score(&input), -390);
+        assert_eq!(ClampAtZero(Subtraction).score(&input), 0);
+    }
+
+    #[test]
+    fn test_phase_multipliers() {
+        let mut policy = PhaseMultipliers::new();
+        policy.set_multiplier(BenchmarkPhase::Prepare, 0.0);
+        policy.set_multiplier(BenchmarkPhase::Load, 1.5);
+
+        assert_eq!(policy.score(&input()), 120);
+    }
+
+    #[test]
+    fn test_penalty_percentage() {
+        let policy = PenaltyPercentage {
+            percent_per_point: 2.0,
+        };
+        assert_eq!(policy.score(&input()), 66);
+
+        let policy = PenaltyPercentage {
+            percent_per_point: 10.0,
+        };
+        assert_eq!(policy.score(&input()), 0);
+    }
+
+    #[test]
+    fn test_low_error_bonus() {
+        let policy = LowErrorBonus {
+            policy: Subtraction,
+            max_error_rate: 0.05,
+            bonus: 50,
+        };
+        assert_eq!(policy.score(&input()), 140);
+
+        let policy = LowErrorBonus {
+            policy: Subtraction,
+            max_error_rate: 0.01,
+            bonus: 50,
+        };
+        assert_eq!(policy.score(&input()), 90);
+    }
+
+    #[test]
+    fn test_closure_policy() {
+        let policy = |input: &ScoringInput| input.total_gain() * 2;
+        assert_eq!(policy.score(&input()), 220);
+    }
+}
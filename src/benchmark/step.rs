@@ -1,27 +1,69 @@
 use crate::agent::*;
+use crate::benchmark::scenario::*;
+use crate::benchmark::timing::*;
 use crate::errors::*;
 use crate::score::*;
 
+use serde::{Deserialize, Serialize};
 use std::future::Future;
 use std::pin::Pin;
 
 pub type BoxFutBenchmarkStep = Pin<Box<dyn Future<Output = BenchmarkStepResult> + Send + 'static>>;
 pub type BenchmarkStep = fn(Agent, Score, Errors) -> BoxFutBenchmarkStep;
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BenchmarkStepResult {
+    step_name: String,
     score: Score,
     errors: Errors,
+    timing: Timing,
+    #[serde(skip)]
+    follow_up_scenarios: Vec<BenchmarkScenario>,
 }
 
 impl BenchmarkStepResult {
     pub fn new(score: Score, errors: Errors) -> BenchmarkStepResult {
         BenchmarkStepResult {
+            step_name: String::new(),
             score: score,
             errors: errors,
+            timing: Timing::default(),
+            follow_up_scenarios: Vec::new(),
         }
     }
 
+    pub fn step_name(&self) -> String {
+        self.step_name.clone()
+    }
+
+    pub fn set_step_name(&mut self, step_name: impl Into<String>) {
+        self.step_name = step_name.into();
+    }
+
+    pub fn score(&self) -> Score {
+        self.score.clone()
+    }
+
+    pub fn errors(&self) -> Errors {
+        self.errors.clone()
+    }
+
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+    }
+
+    pub fn add_follow_up_scenario(&mut self, scenario: BenchmarkScenario) {
+        self.follow_up_scenarios.push(scenario);
+    }
+
+    pub(crate) fn take_follow_up_scenarios(&mut self) -> Vec<BenchmarkScenario> {
+        std::mem::take(&mut self.follow_up_scenarios)
+    }
+
     pub fn total_score(&self) -> isize {
         self.total_gain() - self.total_lose()
     }
@@ -30,6 +72,27 @@ impl BenchmarkStepResult {
         self.score.total() as isize
     }
 
+    pub(crate) fn set_error_origin(&mut self, scenario: &str, step: usize) {
+        self.errors.set_origin(scenario, step, &self.step_name);
+    }
+
+    pub(crate) fn discard_except_failures(&mut self) {
+        self.score.clear();
+        self.errors.retain_failures();
+    }
+
+    pub fn error_records(&self) -> Vec<ErrorRecord> {
+        self.errors.records().cloned().collect()
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.errors.iter().count()
+    }
+
+    pub fn breakdown(&self) -> ScoreBreakdown {
+        self.score.breakdown()
+    }
+
     pub fn total_lose(&self) -> isize {
         self.errors.total_penalty_point() as isize
     }
@@ -44,6 +107,19 @@ impl BenchmarkStepResult {
             _ => false,
         })
     }
+
+    pub fn configuration_errors(&self) -> Vec<ScoreError> {
+        self.score.configuration_errors()
+    }
+}
+
+impl PartialEq for BenchmarkStepResult {
+    fn eq(&self, other: &Self) -> bool {
+        self.step_name == other.step_name
+            && self.score == other.score
+            && self.errors == other.errors
+            && self.timing == other.timing
+    }
 }
 
 #[cfg(test)]
@@ -171,4 +247,46 @@ mod tests {
 
         Ok(())
     }
+
+    #[async_std::test]
+    async fn test_benchmark_step_result_unknown_point_lenient() -> Result<(), ()> {
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+        score.record("a");
+        score.record("typo");
+
+        let benchmark_step_result = BenchmarkStepResult::new(score, Errors::new());
+        assert!(benchmark_step_result.is_success());
+        assert_eq!(benchmark_step_result.total_gain(), 1);
+        assert_eq!(
+            benchmark_step_result.configuration_errors(),
+            vec![ScoreError::UnknownPoint {
+                name: "typo".into(),
+                count: 1
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_benchmark_step_result_unknown_point_try_record() -> Result<(), ()> {
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+        score.record("a");
+
+        let mut errors = Errors::new();
+        if let Err(error) = score.try_record("typo") {
+            errors.record(BenchmarkError::Fail {
+                cause: error.to_string(),
+            });
+        }
+
+        let benchmark_step_result = BenchmarkStepResult::new(score, errors);
+        assert!(benchmark_step_result.is_failure());
+        assert_eq!(benchmark_step_result.total_gain(), 1);
+        assert!(benchmark_step_result.configuration_errors().is_empty());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,49 @@
+use crate::benchmark::scenario::*;
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub started_at: Duration,
+    pub ended_at: Duration,
+}
+
+impl Timing {
+    pub fn new(started_at: Duration, ended_at: Duration) -> Timing {
+        Timing {
+            started_at,
+            ended_at: ended_at.max(started_at),
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.ended_at.saturating_sub(self.started_at)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub phase: BenchmarkPhase,
+    pub timing: Timing,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::benchmark::timing::*;
+
+    #[test]
+    fn test_timing() {
+        let timing = Timing::new(Duration::from_millis(100), Duration::from_millis(350));
+        assert_eq!(timing.duration(), Duration::from_millis(250));
+
+        let timing = Timing::new(Duration::from_millis(100), Duration::from_millis(50));
+        assert_eq!(timing.duration(), Duration::from_secs(0));
+
+        let timing = Timing {
+            started_at: Duration::from_millis(100),
+            ended_at: Duration::from_millis(50),
+        };
+        assert_eq!(timing.duration(), Duration::from_secs(0));
+    }
+}
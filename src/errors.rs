@@ -1,6 +1,14 @@
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
-#[derive(Clone, Error, Debug)]
+const RESPONSE_EXCERPT_LIMIT: usize = 256;
+pub const DEFAULT_PARTICIPANT_MESSAGE_LIMIT: usize = 20;
+
+#[derive(Clone, Error, Debug, PartialEq, Serialize, Deserialize)]
 pub enum BenchmarkError {
     #[error("benchmark fail {cause:?}")]
     Fail { cause: String },
@@ -8,15 +16,243 @@ pub enum BenchmarkError {
     Penalty { cause: String, point: usize },
 }
 
-impl PartialEq for BenchmarkError {
-    fn eq(&self, other: &Self) -> bool {
-        self.to_string() == other.to_string()
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ErrorContext {
+    pub code: Option<String>,
+    pub method: Option<String>,
+    pub path: Option<String>,
+    pub status: Option<u16>,
+    pub scenario: Option<String>,
+    pub step: Option<usize>,
+    pub step_name: Option<String>,
+    pub timestamp: SystemTime,
+    pub response_excerpt: Option<String>,
+}
+
+impl ErrorContext {
+    pub fn new() -> ErrorContext {
+        ErrorContext {
+            code: None,
+            method: None,
+            path: None,
+            status: None,
+            scenario: None,
+            step: None,
+            step_name: None,
+            timestamp: SystemTime::now(),
+            response_excerpt: None,
+        }
+    }
+
+    pub fn request(method: surf::http::Method, path: impl Into<String>) -> ErrorContext {
+        let mut context = ErrorContext::new();
+        context.method = Some(method.to_string());
+        context.path = Some(path.into());
+        context
+    }
+
+    pub async fn from_response(
+        method: surf::http::Method,
+        path: impl Into<String>,
+        response: &mut surf::Response,
+    ) -> ErrorContext {
+        let mut context = ErrorContext::request(method, path);
+        context.status = Some(response.status() as u16);
+        context.response_excerpt = response.body_string().await.ok().map(|body| excerpt(&body));
+        context
+    }
+
+    pub fn from_error(
+        method: surf::http::Method,
+        path: impl Into<String>,
+        error: &surf::Error,
+    ) -> ErrorContext {
+        // surf reports transport failures with a placeholder 500 status, so the
+        // status is only known when a response was received (see from_response)
+        let mut context = ErrorContext::request(method, path);
+        context.response_excerpt = Some(excerpt(&error.to_string()));
+        context
+    }
+}
+
+impl Default for ErrorContext {
+    fn default() -> ErrorContext {
+        ErrorContext::new()
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let timestamp = self
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        write!(
+            f,
+            "at {}.{:03}",
+            timestamp.as_secs(),
+            timestamp.subsec_millis()
+        )?;
+
+        if let Some(scenario) = &self.scenario {
+            write!(f, " scenario {}", scenario)?;
+        }
+        match (&self.step_name, self.step) {
+            (Some(step_name), _) => write!(f, " step {}", step_name)?,
+            (None, Some(step)) => write!(f, " step {}", step)?,
+            (None, None) => {}
+        }
+        if let (Some(method), Some(path)) = (&self.method, &self.path) {
+            write!(f, " {} {}", method, path)?;
+        }
+        if let Some(status) = self.status {
+            write!(f, " status {}", status)?;
+        }
+        if let Some(response_excerpt) = &self.response_excerpt {
+            write!(f, " response {:?}", response_excerpt)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn excerpt(body: &str) -> String {
+    match body.char_indices().nth(RESPONSE_EXCERPT_LIMIT) {
+        Some((index, _)) => format!("{}...", &body[..index]),
+        None => body.to_string(),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ErrorRecord {
+    pub error: BenchmarkError,
+    pub context: ErrorContext,
+}
+
+impl ErrorRecord {
+    pub fn new(error: BenchmarkError, context: ErrorContext) -> ErrorRecord {
+        ErrorRecord { error, context }
+    }
+
+    pub async fn from_response(
+        error: BenchmarkError,
+        method: surf::http::Method,
+        path: impl Into<String>,
+        response: &mut surf::Response,
+    ) -> ErrorRecord {
+        ErrorRecord::new(
+            error,
+            ErrorContext::from_response(method, path, response).await,
+        )
+    }
+
+    pub fn from_error(
+        error: BenchmarkError,
+        method: surf::http::Method,
+        path: impl Into<String>,
+        surf_error: &surf::Error,
+    ) -> ErrorRecord {
+        ErrorRecord::new(error, ErrorContext::from_error(method, path, surf_error))
+    }
+
+    pub fn category(&self) -> &'static str {
+        match self.error {
+            BenchmarkError::Fail { .. } => "fail",
+            BenchmarkError::Penalty { .. } => "penalty",
+        }
+    }
+
+    pub fn code(&self) -> String {
+        match (&self.context.code, &self.error) {
+            (Some(code), _) => code.clone(),
+            (None, BenchmarkError::Fail { cause }) => cause.clone(),
+            (None, BenchmarkError::Penalty { cause, .. }) => cause.clone(),
+        }
+    }
+}
+
+impl From<BenchmarkError> for ErrorRecord {
+    fn from(error: BenchmarkError) -> ErrorRecord {
+        ErrorRecord::new(error, ErrorContext::new())
     }
 }
 
-#[derive(Clone)]
+impl fmt::Display for ErrorRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.error, self.context)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ErrorGroup {
+    pub category: String,
+    pub code: String,
+    pub count: usize,
+    pub penalty_point: usize,
+    pub example: ErrorRecord,
+}
+
+impl fmt::Display for ErrorGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {} x {}", self.category, self.code, self.count)?;
+        if self.penalty_point > 0 {
+            write!(f, " (-{})", self.penalty_point)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn group_error_records<'a>(records: impl Iterator<Item = &'a ErrorRecord>) -> Vec<ErrorGroup> {
+    let mut groups: Vec<ErrorGroup> = Vec::new();
+    let mut indexes: HashMap<(&'static str, String), usize> = HashMap::new();
+
+    for record in records {
+        let category = record.category();
+        let code = record.code();
+        let penalty_point = match record.error {
+            BenchmarkError::Penalty { point, .. } => point,
+            _ => 0,
+        };
+
+        match indexes.entry((category, code)) {
+            Entry::Occupied(entry) => {
+                let group = &mut groups[*entry.get()];
+                group.count += 1;
+                group.penalty_point += penalty_point;
+            }
+            Entry::Vacant(entry) => {
+                groups.push(ErrorGroup {
+                    category: category.to_string(),
+                    code: entry.key().1.clone(),
+                    count: 1,
+                    penalty_point,
+                    example: record.clone(),
+                });
+                entry.insert(groups.len() - 1);
+            }
+        }
+    }
+
+    groups
+}
+
+pub fn participant_messages(groups: &[ErrorGroup], limit: usize) -> Vec<String> {
+    let mut messages: Vec<_> = groups
+        .iter()
+        .take(limit)
+        .map(|group| group.to_string())
+        .collect();
+
+    if groups.len() > limit {
+        messages.push(format!("... and {} more", groups.len() - limit));
+    }
+
+    messages
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Errors {
-    errors: Vec<BenchmarkError>,
+    errors: Vec<ErrorRecord>,
 }
 
 impl Errors {
@@ -24,16 +260,39 @@ impl Errors {
         Errors { errors: Vec::new() }
     }
 
-    pub fn iter(&self) -> std::slice::Iter<'_, BenchmarkError> {
+    pub fn iter(&self) -> impl Iterator<Item = &BenchmarkError> {
+        self.errors.iter().map(|record| &record.error)
+    }
+
+    pub fn records(&self) -> std::slice::Iter<'_, ErrorRecord> {
         self.errors.iter()
     }
 
-    pub fn record(&mut self, error: BenchmarkError) {
-        self.errors.push(error);
+    pub fn record(&mut self, error: impl Into<ErrorRecord>) {
+        self.errors.push(error.into());
+    }
+
+    pub fn groups(&self) -> Vec<ErrorGroup> {
+        group_error_records(self.errors.iter())
+    }
+
+    pub(crate) fn set_origin(&mut self, scenario: &str, step: usize, step_name: &str) {
+        for record in self.errors.iter_mut() {
+            if record.context.scenario.is_none() {
+                record.context.scenario = Some(scenario.to_string());
+                record.context.step = Some(step);
+                record.context.step_name = Some(step_name.to_string());
+            }
+        }
+    }
+
+    pub(crate) fn retain_failures(&mut self) {
+        self.errors
+            .retain(|record| matches!(record.error, BenchmarkError::Fail { .. }));
     }
 
     pub fn total_penalty_point(&self) -> usize {
-        self.errors.iter().fold(0, |total, error| match error {
+        self.iter().fold(0, |total, error| match error {
             BenchmarkError::Penalty { cause: _, point } => total + point,
             _ => total,
         })
@@ -43,6 +302,7 @@ impl Errors {
 #[cfg(test)]
 mod tests {
     use crate::errors::*;
+    use std::convert::TryInto;
 
     #[test]
     fn test_record() {
@@ -78,28 +338,28 @@ mod tests {
         errors.record(error6);
 
         assert_eq!(
-            errors.errors[0].to_string(),
+            errors.errors[0].error.to_string(),
             BenchmarkError::Fail {
                 cause: "error1".into()
             }
             .to_string()
         );
         assert_eq!(
-            errors.errors[1].to_string(),
+            errors.errors[1].error.to_string(),
             BenchmarkError::Fail {
                 cause: "error2".into()
             }
             .to_string()
         );
         assert_eq!(
-            errors.errors[2].to_string(),
+            errors.errors[2].error.to_string(),
             BenchmarkError::Fail {
                 cause: "error3".into()
             }
             .to_string()
         );
         assert_eq!(
-            errors.errors[3].to_string(),
+            errors.errors[3].error.to_string(),
             BenchmarkError::Penalty {
                 cause: "error4".into(),
                 point: 4
@@ -107,7 +367,7 @@ mod tests {
             .to_string()
         );
         assert_eq!(
-            errors.errors[4].to_string(),
+            errors.errors[4].error.to_string(),
             BenchmarkError::Penalty {
                 cause: "error5".into(),
                 point: 5
@@ -115,7 +375,7 @@ mod tests {
             .to_string()
         );
         assert_eq!(
-            errors.errors[5].to_string(),
+            errors.errors[5].error.to_string(),
             BenchmarkError::Penalty {
                 cause: "error6".into(),
                 point: 6
@@ -147,4 +407,214 @@ mod tests {
 
         assert_eq!(errors.total_penalty_point(), 6);
     }
+
+    #[test]
+    fn test_record_with_context() {
+        let mut errors = Errors::new();
+
+        let mut context = ErrorContext::request(surf::http::Method::Post, "/login");
+        context.status = Some(500);
+        errors.record(ErrorRecord::new(
+            BenchmarkError::Fail {
+                cause: "login failed".into(),
+            },
+            context,
+        ));
+        errors.record(BenchmarkError::Penalty {
+            cause: "slow".into(),
+            point: 1,
+        });
+        errors.set_origin("scenario", 2, "login");
+
+        let records: Vec<_> = errors.records().collect();
+        assert_eq!(records[0].context.method, Some("POST".into()));
+        assert_eq!(records[0].context.path, Some("/login".into()));
+        assert_eq!(records[0].context.status, Some(500));
+        assert_eq!(records[0].context.scenario, Some("scenario".into()));
+        assert_eq!(records[0].context.step, Some(2));
+        assert_eq!(records[0].context.step_name, Some("login".into()));
+        assert_eq!(records[1].context.method, None);
+        assert_eq!(records[1].context.scenario, Some("scenario".into()));
+        assert_eq!(errors.total_penalty_point(), 1);
+
+        let output = records[0].to_string();
+        assert!(output.starts_with("benchmark fail \"login failed\" (at "));
+        assert!(output.ends_with("scenario scenario step login POST /login status 500)"));
+    }
+
+    #[test]
+    fn test_excerpt() {
+        assert_eq!(excerpt("short"), "short");
+
+        let body = "あ".repeat(RESPONSE_EXCERPT_LIMIT + 1);
+        let expected = format!("{}...", "あ".repeat(RESPONSE_EXCERPT_LIMIT));
+        assert_eq!(excerpt(&body), expected);
+    }
+
+    #[async_std::test]
+    async fn test_record_from_response() -> Result<(), ()> {
+        let base_url = &mockito::server_url();
+        let path = "/errors/response";
+
+        let _m = mockito::mock("GET", path)
+            .with_status(surf::StatusCode::InternalServerError as usize)
+            .with_body("internal error")
+            .create();
+
+        let client: surf::Client = surf::Config::new()
+            .set_base_url(surf::Url::parse(base_url).unwrap())
+            .try_into()
+            .unwrap();
+        let mut response = client.get(path).await.unwrap();
+
+        let record = ErrorRecord::from_response(
+            BenchmarkError::Fail {
+                cause: "unexpected status".into(),
+            },
+            surf::http::Method::Get,
+            path,
+            &mut response,
+        )
+        .await;
+
+        assert_eq!(record.context.method, Some("GET".into()));
+        assert_eq!(record.context.path, Some(path.into()));
+        assert_eq!(record.context.status, Some(500));
+        assert_eq!(
+            record.context.response_excerpt,
+            Some("internal error".into())
+        );
+
+        Ok(())
+    }
+
+    #[async_std::test]
+    async fn test_record_from_error() -> Result<(), ()> {
+        let path = "/errors/closed";
+
+        let client: surf::Client = surf::Config::new()
+            .set_base_url(surf::Url::parse("http://127.0.0.1:1").unwrap())
+            .try_into()
+            .unwrap();
+        let error = client.get(path).await.unwrap_err();
+
+        let record = ErrorRecord::from_error(
+            BenchmarkError::Fail {
+                cause: "connection refused".into(),
+            },
+            surf::http::Method::Get,
+            path,
+            &error,
+        );
+
+        assert_eq!(record.context.method, Some("GET".into()));
+        assert_eq!(record.context.path, Some(path.into()));
+        assert_eq!(record.context.status, None);
+        assert!(record.context.response_excerpt.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_groups() {
+        let mut errors = Errors::new();
+
+        for _ in 0..3 {
+            errors.record(BenchmarkError::Penalty {
+                cause: "timeout".into(),
+                point: 1,
+            });
+        }
+        errors.record(BenchmarkError::Fail {
+            cause: "timeout".into(),
+        });
+
+        let mut context = ErrorContext::request(surf::http::Method::Get, "/posts/1");
+        context.code = Some("not_found".into());
+        errors.record(ErrorRecord::new(
+            BenchmarkError::Penalty {
+                cause: "missing post 1".into(),
+                point: 2,
+            },
+            context,
+        ));
+        let mut context = ErrorContext::request(surf::http::Method::Get, "/posts/2");
+        context.code = Some("not_found".into());
+        errors.record(ErrorRecord::new(
+            BenchmarkError::Penalty {
+                cause: "missing post 2".into(),
+                point: 2,
+            },
+            context,
+        ));
+
+        let groups = errors.groups();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].to_string(), "[penalty] timeout x 3 (-3)");
+        assert_eq!(groups[1].to_string(), "[fail] timeout x 1");
+        assert_eq!(groups[2].to_string(), "[penalty] not_found x 2 (-4)");
+        assert_eq!(groups[2].example.context.path, Some("/posts/1".into()));
+    }
+
+    #[test]
+    fn test_participant_messages() {
+        let mut errors = Errors::new();
+        for index in 0..5 {
+            errors.record(BenchmarkError::Fail {
+                cause: format!("error{}", index),
+            });
+        }
+        let groups = errors.groups();
+
+        assert_eq!(participant_messages(&groups, 5).len(), 5);
+        assert_eq!(
+            participant_messages(&groups, 2),
+            vec![
+                "[fail] error0 x 1".to_string(),
+                "[fail] error1 x 1".to_string(),
+                "... and 3 more".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_benchmark_error_eq() {
+        let fail = BenchmarkError::Fail {
+            cause: "error".into(),
+        };
+        let penalty = BenchmarkError::Penalty {
+            cause: "error".into(),
+            point: 0,
+        };
+
+        assert_eq!(fail, fail.clone());
+        assert_ne!(fail, penalty);
+        assert_ne!(
+            penalty,
+            BenchmarkError::Penalty {
+                cause: "error".into(),
+                point: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_errors_serde() {
+        let mut errors = Errors::new();
+        errors.record(BenchmarkError::Fail {
+            cause: "error1".into(),
+        });
+        errors.record(ErrorRecord::new(
+            BenchmarkError::Penalty {
+                cause: "error2".into(),
+                point: 2,
+            },
+            ErrorContext::request(surf::http::Method::Get, "/dummy"),
+        ));
+
+        let json = serde_json::to_string(&errors).unwrap();
+        let deserialized: Errors = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, errors);
+    }
 }
@@ -1,4 +1,11 @@
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::match_like_matches_macro,
+    clippy::redundant_field_names
+)]
+
 pub mod agent;
 pub mod benchmark;
 pub mod errors;
+pub mod report;
 pub mod score;
@@ -1,21 +1,45 @@
 extern crate bench_rs;
 #[macro_use]
 extern crate clap;
-extern crate crossbeam_channel;
 extern crate env_logger;
 
 use anyhow::Result;
-use async_std;
+use bench_rs::agent::rate_limiter::*;
 use bench_rs::agent::*;
+use bench_rs::benchmark::initialize::*;
+use bench_rs::benchmark::phase::*;
+use bench_rs::benchmark::readiness::*;
 use bench_rs::benchmark::scenario::*;
 use bench_rs::benchmark::step::*;
 use bench_rs::benchmark::*;
 use bench_rs::errors::*;
+use bench_rs::report::html::*;
+use bench_rs::report::json::*;
+use bench_rs::report::junit::*;
+use bench_rs::report::*;
 use bench_rs::score::*;
-use clap::{App, Arg};
-use log;
-use num_cpus;
+use clap::{App, Arg, ErrorKind};
 use std::env;
+use std::fs;
+use std::time::Duration;
+
+fn parse_rate(rate: &str) -> Result<RateLimiter, String> {
+    let rate = rate.parse::<f64>().map_err(|error| error.to_string())?;
+    RateLimiter::per_second(rate).map_err(|error| error.to_string())
+}
+
+fn parse_status(status: &str) -> Result<u16, String> {
+    let status = status.parse::<u16>().map_err(|error| error.to_string())?;
+    if !(100..=599).contains(&status) {
+        return Err(format!("{} is not an HTTP status code", status));
+    }
+    Ok(status)
+}
+
+fn parse_seconds(seconds: &str) -> Result<Duration, String> {
+    let seconds = seconds.parse::<f64>().map_err(|error| error.to_string())?;
+    Duration::try_from_secs_f64(seconds).map_err(|error| error.to_string())
+}
 
 #[async_std::main]
 async fn main() -> Result<()> {
@@ -41,6 +65,105 @@ async fn main() -> Result<()> {
                 .takes_value(true)
                 .required(false),
         )
+        .arg(
+            Arg::new("rate")
+                .about("benchmark request rate limit per second")
+                .short('r')
+                .long("rate")
+                .value_name("RATE")
+                .takes_value(true)
+                .validator(parse_rate)
+                .required(false),
+        )
+        .arg(
+            Arg::new("wait_for_ready")
+                .about("benchmark target health endpoint path to wait for")
+                .long("wait_for_ready")
+                .value_name("WAIT_FOR_READY")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::new("ready_status")
+                .about("benchmark target health endpoint expected status")
+                .long("ready_status")
+                .value_name("READY_STATUS")
+                .takes_value(true)
+                .validator(parse_status)
+                .required(false),
+        )
+        .arg(
+            Arg::new("ready_max_wait")
+                .about("benchmark target health max wait in seconds")
+                .long("ready_max_wait")
+                .value_name("READY_MAX_WAIT")
+                .takes_value(true)
+                .validator(parse_seconds)
+                .required(false),
+        )
+        .arg(
+            Arg::new("ready_interval")
+                .about("benchmark target health polling interval in seconds")
+                .long("ready_interval")
+                .value_name("READY_INTERVAL")
+                .takes_value(true)
+                .validator(parse_seconds)
+                .required(false),
+        )
+        .arg(
+            Arg::new("initialize")
+                .about("benchmark initialize endpoint path")
+                .short('i')
+                .long("initialize")
+                .value_name("INITIALIZE")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::new("initialize_deadline")
+                .about("benchmark initialize deadline in seconds")
+                .long("initialize_deadline")
+                .value_name("INITIALIZE_DEADLINE")
+                .takes_value(true)
+                .validator(parse_seconds)
+                .required(false),
+        )
+        .arg(
+            Arg::new("warm_up")
+                .about("benchmark warm-up duration in seconds")
+                .short('w')
+                .long("warm_up")
+                .value_name("WARM_UP")
+                .takes_value(true)
+                .validator(parse_seconds)
+                .required(false),
+        )
+        .arg(
+            Arg::new("output")
+                .about("benchmark result output format, json when only an output file is given")
+                .short('o')
+                .long("output")
+                .value_name("OUTPUT")
+                .possible_values(&["text", "json", "junit"])
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::new("output_file")
+                .about("benchmark result output file")
+                .long("output_file")
+                .value_name("OUTPUT_FILE")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::new("html_report")
+                .about("benchmark html report file")
+                .long("html_report")
+                .value_name("HTML_REPORT")
+                .takes_value(true)
+                .required(false),
+        )
         .get_matches();
 
     let base_url = matches.value_of("base_url").unwrap();
@@ -48,6 +171,42 @@ async fn main() -> Result<()> {
         Some(parallels) => parallels.parse::<usize>().unwrap(),
         None => num_cpus::get(),
     };
+    let rate_limiter = matches
+        .value_of("rate")
+        .map(|rate| parse_rate(rate).unwrap());
+    let readiness = matches.value_of("wait_for_ready").map(|path| {
+        let mut readiness = Readiness::new(path);
+        if let Some(ready_status) = matches.value_of("ready_status") {
+            readiness.set_expected_status(parse_status(ready_status).unwrap());
+        }
+        if let Some(ready_max_wait) = matches.value_of("ready_max_wait") {
+            readiness.set_max_wait(parse_seconds(ready_max_wait).unwrap());
+        }
+        if let Some(ready_interval) = matches.value_of("ready_interval") {
+            readiness.set_interval(parse_seconds(ready_interval).unwrap());
+        }
+        readiness
+    });
+    let initialize = matches.value_of("initialize");
+    let initialize_deadline = match matches.value_of("initialize_deadline") {
+        Some(initialize_deadline) => parse_seconds(initialize_deadline).unwrap(),
+        None => Duration::from_secs(10),
+    };
+    let warm_up = matches
+        .value_of("warm_up")
+        .map(|warm_up| parse_seconds(warm_up).unwrap());
+    let output_file = matches.value_of("output_file");
+    let output = match (matches.value_of("output"), output_file) {
+        (Some("text"), Some(_)) => clap::Error::with_description(
+            "--output_file requires --output json or --output junit\n".into(),
+            ErrorKind::ArgumentConflict,
+        )
+        .exit(),
+        (Some(output), _) => output,
+        (None, Some(_)) => "json",
+        (None, None) => "text",
+    };
+    let html_report_file = matches.value_of("html_report");
 
     let key = "RUST_LOG";
     match env::var("RUST_LOG") {
@@ -85,22 +244,34 @@ async fn main() -> Result<()> {
     }
 
     let mut prepare_scenario = BenchmarkScenario::new("prepare_scenario");
-    prepare_scenario.add_benchmark_step(prepare_step);
+    prepare_scenario.add_named_benchmark_step("prepare_step", prepare_step);
 
     let mut load_scenario1 = BenchmarkScenario::new("load_scenario1");
-    load_scenario1.add_benchmark_step(load_step);
+    load_scenario1.add_named_benchmark_step("load_step", load_step);
 
     let mut load_scenario2 = BenchmarkScenario::new("load_scenario2");
-    load_scenario2.add_benchmark_step(load_step);
+    load_scenario2.add_named_benchmark_step("load_step", load_step);
 
     let mut validation_scenario = BenchmarkScenario::new("validation_scenario");
-    validation_scenario.add_benchmark_step(validation_step);
+    validation_scenario.add_named_benchmark_step("validation_step", validation_step);
 
     let mut benchmark = Benchmark::new(agent, score, errors, parallels);
     benchmark.add_prepare_scenario(prepare_scenario);
     benchmark.add_load_scenario(load_scenario1);
     benchmark.add_load_scenario(load_scenario2);
     benchmark.add_validation_scenario(validation_scenario);
+    if let Some(rate_limiter) = rate_limiter {
+        benchmark.set_rate_limiter(rate_limiter);
+    }
+    if let Some(readiness) = readiness {
+        benchmark.set_readiness(readiness);
+    }
+    if let Some(initialize) = initialize {
+        benchmark.set_initialize(Initialize::new(initialize, initialize_deadline));
+    }
+    if let Some(warm_up) = warm_up {
+        benchmark.set_warm_up(WarmUp::Duration(warm_up));
+    }
 
     let benchmark_result = benchmark.start().await;
 
@@ -112,18 +283,132 @@ async fn main() -> Result<()> {
             benchmark_result.total_lose()
         );
 
+        for (name, point) in benchmark_result.breakdown() {
+            log::info!("  {} : {} x {}", name, point.subtotal, point.count);
+        }
+
         log::info!("Detail:");
         for result in benchmark_result.details() {
             log::info!(
-                "  {} : {} ({} - {})",
+                "  {} : {} ({} - {}) in {:?}",
                 result.scenario_name(),
                 result.total_score(),
                 result.total_gain(),
-                result.total_lose()
+                result.total_lose(),
+                result.timing().duration()
             );
+            for (name, point) in result.breakdown() {
+                log::info!("    {} : {} x {}", name, point.subtotal, point.count);
+            }
+            for step_result in result.step_results() {
+                log::info!(
+                    "    {}/{} : {} ({} - {}) {} in {:?}",
+                    result.scenario_name(),
+                    step_result.step_name(),
+                    step_result.total_score(),
+                    step_result.total_gain(),
+                    step_result.total_lose(),
+                    if step_result.is_success() {
+                        "success"
+                    } else {
+                        "failure"
+                    },
+                    step_result.timing().duration()
+                );
+            }
         }
     } else {
         log::info!("Failure");
+        if let Some(readiness_error) = benchmark_result.readiness_error() {
+            log::info!("  {}", readiness_error);
+        }
+        if let Some(initialize_error) = benchmark_result.initialize_error() {
+            log::info!("  {}", initialize_error);
+        }
+        for result in benchmark_result.details() {
+            for failed_step in result.failed_steps() {
+                log::info!("  {} failed", failed_step);
+            }
+        }
+        for breach in benchmark_result.budget_breaches() {
+            log::info!("  {}", breach);
+        }
+    }
+
+    for message in benchmark_result.participant_messages(DEFAULT_PARTICIPANT_MESSAGE_LIMIT) {
+        log::info!("Error: {}", message);
+    }
+    for record in benchmark_result.error_records() {
+        log::debug!("Error detail: {}", record);
+    }
+
+    for error in benchmark_result.configuration_errors() {
+        log::warn!("Configuration error: {}", error);
+    }
+
+    for phase_result in benchmark_result.phase_results() {
+        if phase_result.is_skipped() {
+            log::info!("Phase {:?}: skipped", phase_result.phase());
+            continue;
+        }
+        log::info!(
+            "Phase {:?}: {} / Score: {} ({} - {}) in {:?}",
+            phase_result.phase(),
+            if phase_result.is_success() {
+                "success"
+            } else {
+                "failure"
+            },
+            phase_result.total_score(),
+            phase_result.total_gain(),
+            phase_result.total_lose(),
+            phase_result
+                .timing()
+                .map(|timing| timing.duration())
+                .unwrap_or_default()
+        );
+    }
+
+    let agent_metrics = benchmark_result.agent_metrics();
+    log::info!(
+        "Requests: {} / Latency: {:?} / Rate limit wait: {:?}",
+        agent_metrics.requests,
+        agent_metrics.latency,
+        agent_metrics.rate_limit_wait
+    );
+    let scenario_counts = benchmark_result.scenario_counts();
+    log::info!(
+        "Scenarios: {} processed / {} canceled / {} timed out / {} skipped (completion {:.1}%)",
+        scenario_counts.processed,
+        scenario_counts.canceled,
+        scenario_counts.timed_out,
+        scenario_counts.skipped,
+        scenario_counts.completion_ratio() * 100.0
+    );
+    if benchmark_result.dropped_starts() > 0 {
+        log::info!("Dropped starts: {}", benchmark_result.dropped_starts());
+    }
+    for event in benchmark_result.timeline() {
+        log::info!("Timeline: {:?}", event);
+    }
+
+    let mut metadata = RunMetadata::new();
+    metadata.set_base_url(base_url);
+    metadata.set_parallels(parallels);
+
+    let report = match output {
+        "json" => Some(JsonReport::new(&benchmark_result, &metadata).to_json()?),
+        "junit" => Some(junit_xml(&benchmark_result, &metadata)),
+        _ => None,
+    };
+    if let Some(report) = report {
+        match output_file {
+            Some(output_file) => fs::write(output_file, report)?,
+            None => println!("{}", report),
+        }
+    }
+    if let Some(html_report_file) = html_report_file {
+        fs::write(html_report_file, html_report(&benchmark_result, &metadata))?;
     }
 
     Ok(())
@@ -0,0 +1,249 @@
+pub mod html;
+pub mod json;
+pub mod junit;
+
+use crate::agent::stats::*;
+use crate::benchmark::scenario::*;
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunMetadata {
+    pub tool: String,
+    pub version: String,
+    pub base_url: Option<String>,
+    pub parallels: Option<usize>,
+    pub generated_at: SystemTime,
+}
+
+impl RunMetadata {
+    pub fn new() -> RunMetadata {
+        RunMetadata {
+            tool: env!("CARGO_PKG_NAME").into(),
+            version: env!("CARGO_PKG_VERSION").into(),
+            base_url: None,
+            parallels: None,
+            generated_at: SystemTime::now(),
+        }
+    }
+
+    pub fn set_base_url(&mut self, base_url: impl Into<String>) {
+        self.base_url = Some(base_url.into());
+    }
+
+    pub fn set_parallels(&mut self, parallels: usize) {
+        self.parallels = Some(parallels);
+    }
+}
+
+impl Default for RunMetadata {
+    fn default() -> RunMetadata {
+        RunMetadata::new()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EndpointLatency {
+    pub method: String,
+    pub path: String,
+    pub count: usize,
+    pub errors: usize,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+pub fn endpoint_latencies(endpoint_stats: &[EndpointStats]) -> Vec<EndpointLatency> {
+    endpoint_stats
+        .iter()
+        .map(|stats| EndpointLatency {
+            method: stats.method.clone(),
+            path: stats.path.clone(),
+            count: stats.requests(),
+            errors: stats.errors,
+            p50: stats.latency.percentile(50.0),
+            p90: stats.latency.percentile(90.0),
+            p95: stats.latency.percentile(95.0),
+            p99: stats.latency.percentile(99.0),
+            max: stats.latency.max(),
+        })
+        .collect()
+}
+
+pub fn request_timeline(endpoint_stats: &[EndpointStats]) -> RequestTimeline {
+    let mut timeline = RequestTimeline::new();
+    for stats in endpoint_stats {
+        timeline.merge(&stats.timeline);
+    }
+    timeline
+}
+
+pub(crate) fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // control characters other than tab and newlines are not allowed in XML 1.0
+            '\u{0}'..='\u{8}'
+            | '\u{B}'
+            | '\u{C}'
+            | '\u{E}'..='\u{1F}'
+            | '\u{FFFE}'
+            | '\u{FFFF}' => escaped.push('\u{FFFD}'),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub(crate) fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+pub(crate) fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+pub(crate) fn iso8601(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, secs) = (secs / 86400, secs % 86400);
+
+    // civil date from days since 1970-01-01
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+pub(crate) fn status_name(status: ScenarioStatus) -> &'static str {
+    match status {
+        ScenarioStatus::Processed => "processed",
+        ScenarioStatus::Canceled => "canceled",
+        ScenarioStatus::TimedOut => "timed_out",
+        ScenarioStatus::Skipped => "skipped",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape("<a href=\"x\">&'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&apos;&lt;/a&gt;"
+        );
+        assert_eq!(escape("tab\tnew\nline\r"), "tab\tnew\nline\r");
+        assert_eq!(
+            escape("nul\u{0}bell\u{7}vt\u{B}ff\u{C}esc\u{1B}\u{FFFF}"),
+            "nul\u{FFFD}bell\u{FFFD}vt\u{FFFD}ff\u{FFFD}esc\u{FFFD}\u{FFFD}"
+        );
+    }
+
+    #[test]
+    fn test_endpoint_latencies() {
+        let mut a = EndpointStats::new("GET", "/a");
+        for ms in 1..=100 {
+            a.record(
+                Duration::from_millis(0),
+                Duration::from_millis(ms),
+                Some(200),
+            );
+        }
+        let mut b = EndpointStats::new("GET", "/b");
+        b.record(
+            Duration::from_millis(0),
+            Duration::from_millis(7),
+            Some(500),
+        );
+
+        let latencies = endpoint_latencies(&[a, b]);
+        assert_eq!(latencies.len(), 2);
+        assert_eq!(latencies[0].path, "/a");
+        assert_eq!(latencies[0].count, 100);
+        assert_eq!(latencies[0].errors, 0);
+        assert!(latencies[0].p50 >= Duration::from_millis(50));
+        assert!(latencies[0].p50 < latencies[0].p90);
+        assert!(latencies[0].p90 <= latencies[0].p99);
+        assert_eq!(latencies[0].max, Duration::from_millis(100));
+        assert_eq!(latencies[1].errors, 1);
+        assert_eq!(latencies[1].p50, Duration::from_millis(7));
+        assert_eq!(latencies[1].p99, Duration::from_millis(7));
+    }
+
+    #[test]
+    fn test_request_timeline() {
+        let mut a = EndpointStats::new("GET", "/a");
+        a.record(
+            Duration::from_millis(100),
+            Duration::from_millis(1),
+            Some(200),
+        );
+        a.record(
+            Duration::from_millis(900),
+            Duration::from_millis(1),
+            Some(500),
+        );
+        let mut b = EndpointStats::new("POST", "/b");
+        b.record(
+            Duration::from_millis(25_000),
+            Duration::from_millis(1),
+            Some(200),
+        );
+
+        let timeline = request_timeline(&[a, b]);
+        assert_eq!(timeline.bucket(), Duration::from_millis(800));
+        assert_eq!(
+            timeline.buckets()[0],
+            TimelineBucket {
+                offset: Duration::from_secs(0),
+                requests: 1,
+                errors: 0
+            }
+        );
+        assert_eq!(timeline.buckets()[1].errors, 1);
+        assert_eq!(timeline.buckets().last().unwrap().requests, 1);
+        assert!(request_timeline(&[]).buckets().is_empty());
+    }
+
+    #[test]
+    fn test_iso8601() {
+        assert_eq!(iso8601(UNIX_EPOCH), "1970-01-01T00:00:00");
+        assert_eq!(
+            iso8601(UNIX_EPOCH + Duration::from_secs(951_782_400 + 3661)),
+            "2000-02-29T01:01:01"
+        );
+        assert_eq!(
+            iso8601(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            "2023-11-14T22:13:20"
+        );
+    }
+}
@@ -0,0 +1,347 @@
+use crate::benchmark::*;
+use crate::report::*;
+
+use std::fmt::Write;
+
+const CHART_WIDTH: f64 = 720.0;
+const CHART_HEIGHT: f64 = 200.0;
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#222}\
+table{border-collapse:collapse;margin-bottom:2em}\
+th,td{border:1px solid #ccc;padding:4px 8px;text-align:right}\
+th:first-child,td:first-child{text-align:left}\
+.success{color:#1a7f37}.failure{color:#cf222e}.skipped{color:#9a6700}\
+.requests{fill:#4c78a8}.errors{fill:#e45756}";
+
+pub fn html_report(result: &BenchmarkResult, metadata: &RunMetadata) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(
+        html,
+        "<title>{} report</title>\n<style>{}</style>\n</head>\n<body>",
+        escape(&metadata.tool),
+        STYLE
+    );
+
+    write_summary(&mut html, result, metadata);
+    write_phases(&mut html, result);
+    write_scenarios(&mut html, result);
+    write_error_groups(&mut html, result);
+    write_endpoint_latencies(&mut html, result);
+    write_timeline(&mut html, result);
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn write_summary(html: &mut String, result: &BenchmarkResult, metadata: &RunMetadata) {
+    let (class, outcome) = if result.is_success() {
+        ("success", "Success")
+    } else {
+        ("failure", "Failure")
+    };
+
+    let _ = writeln!(
+        html,
+        "<h1>{} {}</h1>\n<p>{}{}</p>",
+        escape(&metadata.tool),
+        escape(&metadata.version),
+        iso8601(metadata.generated_at),
+        metadata
+            .base_url
+            .as_ref()
+            .map(|base_url| format!(" / {}", escape(base_url)))
+            .unwrap_or_default()
+    );
+    let _ = writeln!(
+        html,
+        "<h2 class=\"{}\">{} / Score: {} ({} - {})</h2>",
+        class,
+        outcome,
+        result.total_score(),
+        result.total_gain(),
+        result.total_lose()
+    );
+
+    let scenario_counts = result.scenario_counts();
+    let _ = writeln!(
+        html,
+        "<p>Scenarios: {} processed / {} canceled / {} timed out / {} skipped (completion {:.1}%)</p>",
+        scenario_counts.processed,
+        scenario_counts.canceled,
+        scenario_counts.timed_out,
+        scenario_counts.skipped,
+        scenario_counts.completion_ratio() * 100.0
+    );
+
+    let mut failures: Vec<_> = result
+        .budget_breaches()
+        .iter()
+        .map(|breach| breach.to_string())
+        .collect();
+    if let Some(initialize_error) = result.initialize_error() {
+        failures.insert(0, initialize_error.to_string());
+    }
+    if let Some(readiness_error) = result.readiness_error() {
+        failures.insert(0, readiness_error.to_string());
+    }
+    if !failures.is_empty() {
+        html.push_str("<ul class=\"failure\">\n");
+        for failure in failures {
+            let _ = writeln!(html, "<li>{}</li>", escape(&failure));
+        }
+        html.push_str("</ul>\n");
+    }
+}
+
+fn write_phases(html: &mut String, result: &BenchmarkResult) {
+    html.push_str("<h2>Phases</h2>\n<table>\n");
+    html.push_str(
+        "<tr><th>Phase</th><th>Outcome</th><th>Score</th><th>Start (ms)</th><th>End (ms)</th><th>Duration (ms)</th></tr>\n",
+    );
+    for phase_result in result.phase_results() {
+        let (class, outcome) = if phase_result.is_skipped() {
+            ("skipped", "skipped")
+        } else if phase_result.is_success() {
+            ("success", "success")
+        } else {
+            ("failure", "failure")
+        };
+        let timing = match phase_result.timing() {
+            Some(timing) => format!(
+                "<td>{:.1}</td><td>{:.1}</td><td>{:.1}</td>",
+                millis(timing.started_at),
+                millis(timing.ended_at),
+                millis(timing.duration())
+            ),
+            None => "<td>-</td><td>-</td><td>-</td>".into(),
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"{}\">{}</td><td>{}</td>{}</tr>",
+            escape(&phase_result.phase().name()),
+            class,
+            outcome,
+            phase_result.total_score(),
+            timing
+        );
+    }
+    let _ = writeln!(
+        html,
+        "<tr><th>total</th><td></td><td>{}</td><td>{:.1}</td><td>{:.1}</td><td>{:.1}</td></tr>",
+        result.total_score(),
+        millis(result.timing().started_at),
+        millis(result.timing().ended_at),
+        millis(result.timing().duration())
+    );
+    html.push_str("</table>\n");
+}
+
+fn write_scenarios(html: &mut String, result: &BenchmarkResult) {
+    html.push_str("<h2>Scenarios</h2>\n<table>\n");
+    html.push_str(
+        "<tr><th>Scenario</th><th>Phase</th><th>Outcome</th><th>Score</th><th>Gain</th><th>Lose</th><th>Errors</th><th>Duration (ms)</th></tr>\n",
+    );
+    for detail in result
+        .details()
+        .into_iter()
+        .chain(result.unprocessed_details())
+    {
+        let (class, outcome) = match detail.status() {
+            ScenarioStatus::Processed if detail.is_success() => ("success", "success"),
+            ScenarioStatus::Processed => ("failure", "failure"),
+            status => ("skipped", status_name(status)),
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td class=\"{}\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.1}</td></tr>",
+            escape(&detail.scenario_name()),
+            detail
+                .phase()
+                .map(|phase| escape(&phase.name()))
+                .unwrap_or_else(|| "-".into()),
+            class,
+            outcome,
+            detail.total_score(),
+            detail.total_gain(),
+            detail.total_lose(),
+            detail.error_count(),
+            millis(detail.timing().duration())
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn write_error_groups(html: &mut String, result: &BenchmarkResult) {
+    html.push_str("<h2>Errors</h2>\n<table>\n");
+    html.push_str(
+        "<tr><th>Code</th><th>Category</th><th>Count</th><th>Penalty</th><th>Example</th></tr>\n",
+    );
+    for group in result.error_groups() {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&group.code),
+            escape(&group.category),
+            group.count,
+            group.penalty_point,
+            escape(&group.example.to_string())
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn write_endpoint_latencies(html: &mut String, result: &BenchmarkResult) {
+    html.push_str("<h2>Latency (ms)</h2>\n<table>\n");
+    html.push_str(
+        "<tr><th>Endpoint</th><th>Requests</th><th>Errors</th><th>p50</th><th>p90</th><th>p95</th><th>p99</th><th>max</th></tr>\n",
+    );
+    for endpoint in endpoint_latencies(&result.endpoint_stats()) {
+        let _ = writeln!(
+            html,
+            "<tr><td>{} {}</td><td>{}</td><td>{}</td><td>{:.1}</td><td>{:.1}</td><td>{:.1}</td><td>{:.1}</td><td>{:.1}</td></tr>",
+            escape(&endpoint.method),
+            escape(&endpoint.path),
+            endpoint.count,
+            endpoint.errors,
+            millis(endpoint.p50),
+            millis(endpoint.p90),
+            millis(endpoint.p95),
+            millis(endpoint.p99),
+            millis(endpoint.max)
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn write_timeline(html: &mut String, result: &BenchmarkResult) {
+    let request_timeline = request_timeline(&result.endpoint_stats());
+    let bucket = request_timeline.bucket();
+    let timeline = request_timeline.buckets();
+
+    let _ = writeln!(
+        html,
+        "<h2>Throughput and errors (per {:.1}s)</h2>",
+        bucket.as_secs_f64()
+    );
+    let _ = writeln!(
+        html,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+        CHART_WIDTH, CHART_HEIGHT, CHART_WIDTH, CHART_HEIGHT
+    );
+
+    let max_requests = timeline
+        .iter()
+        .map(|timeline_bucket| timeline_bucket.requests)
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    let width = CHART_WIDTH / timeline.len().max(1) as f64;
+    for (index, timeline_bucket) in timeline.iter().enumerate() {
+        let x = index as f64 * width;
+        for (class, count) in [
+            ("requests", timeline_bucket.requests),
+            ("errors", timeline_bucket.errors),
+        ] {
+            if count == 0 {
+                continue;
+            }
+            let height = count as f64 / max_requests * (CHART_HEIGHT - 20.0);
+            let _ = writeln!(
+                html,
+                "<rect class=\"{}\" x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\"><title>{:.1}s: {} {}</title></rect>",
+                class,
+                x,
+                CHART_HEIGHT - height,
+                width * 0.9,
+                height,
+                timeline_bucket.offset.as_secs_f64(),
+                count,
+                class
+            );
+        }
+    }
+    let _ = writeln!(
+        html,
+        "<text x=\"4\" y=\"14\" font-size=\"12\">max {} requests</text>",
+        max_requests
+    );
+    html.push_str("</svg>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::agent::stats::*;
+    use crate::benchmark::scenario::*;
+    use crate::benchmark::step::*;
+    use crate::errors::*;
+    use crate::report::html::*;
+    use crate::score::*;
+    use std::time::UNIX_EPOCH;
+
+    fn benchmark_result() -> BenchmarkResult {
+        let mut score = Score::new();
+        score.add_point_table("a", 3);
+        score.record("a");
+
+        let mut errors = Errors::new();
+        errors.record(BenchmarkError::Penalty {
+            cause: "<slow>".into(),
+            point: 1,
+        });
+
+        let mut scenario_result = BenchmarkScenarioResult::new("scenario1");
+        scenario_result.set_phase(BenchmarkPhase::Load);
+        scenario_result.add_step_result(BenchmarkStepResult::new(score, errors));
+
+        let mut benchmark_result = BenchmarkResult::new();
+        benchmark_result.add_scenario_result(scenario_result);
+
+        let mut get_posts = EndpointStats::new("GET", "/posts");
+        get_posts.record(
+            Duration::from_millis(0),
+            Duration::from_millis(12),
+            Some(200),
+        );
+        let mut post_posts = EndpointStats::new("POST", "/posts");
+        post_posts.record(
+            Duration::from_millis(150),
+            Duration::from_millis(30),
+            Some(500),
+        );
+        benchmark_result.set_endpoint_stats(vec![get_posts, post_posts]);
+        benchmark_result
+    }
+
+    #[test]
+    fn test_html_report() {
+        let mut metadata = RunMetadata::new();
+        metadata.generated_at = UNIX_EPOCH;
+
+        let html = html_report(&benchmark_result(), &metadata);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h2 class=\"success\">Success / Score: 2 (3 - 1)</h2>"));
+        assert!(html.contains("<tr><td>scenario1</td><td>load</td><td class=\"success\">success</td><td>2</td><td>3</td><td>1</td><td>1</td><td>0.0</td></tr>"));
+        assert!(html.contains(
+            "<tr><td>load</td><td class=\"success\">success</td><td>2</td><td>-</td><td>-</td><td>-</td></tr>"
+        ));
+        assert!(html.contains(
+            "<p>Scenarios: 1 processed / 0 canceled / 0 timed out / 0 skipped (completion 100.0%)</p>"
+        ));
+        assert!(html.contains("<tr><td>&lt;slow&gt;</td><td>penalty</td><td>1</td><td>1</td>"));
+        assert!(html.contains("<tr><td>GET /posts</td><td>1</td><td>0</td><td>12.0</td>"));
+        assert!(html.contains("<tr><td>POST /posts</td><td>1</td><td>1</td><td>30.0</td>"));
+        assert_eq!(html.matches("<rect class=\"requests\"").count(), 2);
+        assert_eq!(html.matches("<rect class=\"errors\"").count(), 1);
+    }
+
+    #[test]
+    fn test_html_report_self_contained() {
+        let html = html_report(&benchmark_result(), &RunMetadata::new());
+
+        assert!(!html.contains("<script src"));
+        assert!(!html.contains("<link"));
+        assert!(!html.contains("https://"));
+    }
+}
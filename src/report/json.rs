@@ -0,0 +1,597 @@
+use crate::agent::stats::*;
+use crate::benchmark::load::*;
+use crate::benchmark::phase::*;
+use crate::benchmark::scenario::*;
+use crate::benchmark::step::*;
+use crate::benchmark::timing::*;
+use crate::benchmark::*;
+use crate::errors::*;
+use crate::report::*;
+use crate::score::*;
+
+use serde::{Deserialize, Serialize};
+
+pub const JSON_SCHEMA_VERSION: u32 = 2;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonReport {
+    pub schema_version: u32,
+    pub metadata: JsonMetadata,
+    pub success: bool,
+    pub score: JsonScore,
+    pub timing: JsonTiming,
+    pub phases: Vec<JsonPhase>,
+    pub breakdown: ScoreBreakdown,
+    pub scenarios: Vec<JsonScenario>,
+    pub scenario_counts: JsonScenarioCounts,
+    pub error_groups: Vec<JsonErrorGroup>,
+    pub budget_breaches: Vec<String>,
+    pub readiness_error: Option<String>,
+    pub initialize_error: Option<String>,
+    pub configuration_errors: Vec<String>,
+    pub timings: JsonTimings,
+    pub warm_up_requests: usize,
+    pub endpoint_stats: Vec<JsonEndpointStats>,
+    pub dropped_starts: usize,
+    pub timeline: Vec<JsonTimelineEvent>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonMetadata {
+    pub tool: String,
+    pub version: String,
+    pub base_url: Option<String>,
+    pub parallels: Option<usize>,
+    pub generated_at_ms: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonScore {
+    pub total: isize,
+    pub gain: isize,
+    pub lose: isize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonTiming {
+    pub started_at_ms: f64,
+    pub ended_at_ms: f64,
+    pub duration_ms: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonPhase {
+    pub phase: String,
+    pub scored: bool,
+    pub skipped: bool,
+    pub success: bool,
+    pub score: JsonScore,
+    pub penalty_point: usize,
+    pub timing: Option<JsonTiming>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonScenario {
+    pub name: String,
+    pub phase: Option<String>,
+    pub stage: Option<usize>,
+    pub status: String,
+    pub success: bool,
+    pub score: JsonScore,
+    pub timing: JsonTiming,
+    pub steps: Vec<JsonStep>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonScenarioCounts {
+    pub processed: usize,
+    pub canceled: usize,
+    pub timed_out: usize,
+    pub skipped: usize,
+    pub completion_ratio: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonStep {
+    pub index: usize,
+    pub name: String,
+    pub success: bool,
+    pub score: JsonScore,
+    pub timing: JsonTiming,
+    pub errors: Vec<JsonError>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonError {
+    pub kind: String,
+    pub cause: String,
+    pub point: usize,
+    pub code: String,
+    pub method: Option<String>,
+    pub path: Option<String>,
+    pub status: Option<u16>,
+    pub scenario: Option<String>,
+    pub step: Option<usize>,
+    pub step_name: Option<String>,
+    pub timestamp_ms: u64,
+    pub response_excerpt: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonErrorGroup {
+    pub category: String,
+    pub code: String,
+    pub count: usize,
+    pub penalty_point: usize,
+    pub example: JsonError,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonTimings {
+    pub requests: usize,
+    pub latency_ms: f64,
+    pub rate_limit_wait_ms: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonEndpointStats {
+    pub method: String,
+    pub path: String,
+    pub requests: usize,
+    pub errors: usize,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    pub timeline_bucket_ms: f64,
+    pub timeline: Vec<JsonTimelineBucket>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonTimelineBucket {
+    pub offset_ms: f64,
+    pub requests: usize,
+    pub errors: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JsonTimelineEvent {
+    ConcurrencyAdjusted {
+        elapsed_ms: f64,
+        from: usize,
+        to: usize,
+        error_rate: f64,
+        latency_ms: f64,
+    },
+}
+
+impl JsonReport {
+    pub fn new(result: &BenchmarkResult, metadata: &RunMetadata) -> JsonReport {
+        let agent_metrics = result.agent_metrics();
+        let scenario_counts = result.scenario_counts();
+
+        JsonReport {
+            schema_version: JSON_SCHEMA_VERSION,
+            metadata: JsonMetadata {
+                tool: metadata.tool.clone(),
+                version: metadata.version.clone(),
+                base_url: metadata.base_url.clone(),
+                parallels: metadata.parallels,
+                generated_at_ms: unix_millis(metadata.generated_at),
+            },
+            success: result.is_success(),
+            score: JsonScore {
+                total: result.total_score(),
+                gain: result.total_gain(),
+                lose: result.total_lose(),
+            },
+            timing: json_timing(result.timing()),
+            phases: result.phase_results().iter().map(json_phase).collect(),
+            breakdown: result.breakdown(),
+            scenarios: result
+                .details()
+                .iter()
+                .chain(&result.unprocessed_details())
+                .map(json_scenario)
+                .collect(),
+            scenario_counts: JsonScenarioCounts {
+                processed: scenario_counts.processed,
+                canceled: scenario_counts.canceled,
+                timed_out: scenario_counts.timed_out,
+                skipped: scenario_counts.skipped,
+                completion_ratio: scenario_counts.completion_ratio(),
+            },
+            error_groups: result
+                .error_groups()
+                .iter()
+                .map(|group| JsonErrorGroup {
+                    category: group.category.clone(),
+                    code: group.code.clone(),
+                    count: group.count,
+                    penalty_point: group.penalty_point,
+                    example: json_error(&group.example),
+                })
+                .collect(),
+            budget_breaches: result
+                .budget_breaches()
+                .iter()
+                .map(|breach| breach.to_string())
+                .collect(),
+            readiness_error: result.readiness_error().map(|error| error.to_string()),
+            initialize_error: result.initialize_error().map(|error| error.to_string()),
+            configuration_errors: result
+                .configuration_errors()
+                .iter()
+                .map(|error| error.to_string())
+                .collect(),
+            timings: JsonTimings {
+                requests: agent_metrics.requests,
+                latency_ms: millis(agent_metrics.latency),
+                rate_limit_wait_ms: millis(agent_metrics.rate_limit_wait),
+            },
+            warm_up_requests: result.warm_up_requests(),
+            endpoint_stats: result
+                .endpoint_stats()
+                .iter()
+                .map(json_endpoint_stats)
+                .collect(),
+            dropped_starts: result.dropped_starts(),
+            timeline: result.timeline().iter().map(json_timeline_event).collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+fn json_score(total: isize, gain: isize, lose: isize) -> JsonScore {
+    JsonScore { total, gain, lose }
+}
+
+fn json_timing(timing: Timing) -> JsonTiming {
+    JsonTiming {
+        started_at_ms: millis(timing.started_at),
+        ended_at_ms: millis(timing.ended_at),
+        duration_ms: millis(timing.duration()),
+    }
+}
+
+fn json_phase(result: &PhaseResult) -> JsonPhase {
+    JsonPhase {
+        phase: result.phase().name(),
+        scored: result.is_scored(),
+        skipped: result.is_skipped(),
+        success: result.is_success(),
+        score: json_score(
+            result.total_score(),
+            result.total_gain(),
+            result.total_lose(),
+        ),
+        penalty_point: result.penalty_point(),
+        timing: result.timing().map(json_timing),
+    }
+}
+
+fn json_scenario(result: &BenchmarkScenarioResult) -> JsonScenario {
+    JsonScenario {
+        name: result.scenario_name(),
+        phase: result.phase().map(|phase| phase.name()),
+        stage: result.stage(),
+        status: status_name(result.status()).into(),
+        success: result.is_success(),
+        score: json_score(
+            result.total_score(),
+            result.total_gain(),
+            result.total_lose(),
+        ),
+        timing: json_timing(result.timing()),
+        steps: result
+            .step_results()
+            .iter()
+            .enumerate()
+            .map(|(index, step_result)| json_step(index, step_result))
+            .collect(),
+    }
+}
+
+fn json_step(index: usize, result: &BenchmarkStepResult) -> JsonStep {
+    JsonStep {
+        index,
+        name: result.step_name(),
+        success: result.is_success(),
+        score: json_score(
+            result.total_score(),
+            result.total_gain(),
+            result.total_lose(),
+        ),
+        timing: json_timing(result.timing()),
+        errors: result.error_records().iter().map(json_error).collect(),
+    }
+}
+
+fn json_error(record: &ErrorRecord) -> JsonError {
+    let (cause, point) = match &record.error {
+        BenchmarkError::Fail { cause } => (cause.clone(), 0),
+        BenchmarkError::Penalty { cause, point } => (cause.clone(), *point),
+    };
+
+    JsonError {
+        kind: record.category().into(),
+        cause,
+        point,
+        code: record.code(),
+        method: record.context.method.clone(),
+        path: record.context.path.clone(),
+        status: record.context.status,
+        scenario: record.context.scenario.clone(),
+        step: record.context.step,
+        step_name: record.context.step_name.clone(),
+        timestamp_ms: unix_millis(record.context.timestamp),
+        response_excerpt: record.context.response_excerpt.clone(),
+    }
+}
+
+fn json_endpoint_stats(stats: &EndpointStats) -> JsonEndpointStats {
+    JsonEndpointStats {
+        method: stats.method.clone(),
+        path: stats.path.clone(),
+        requests: stats.requests(),
+        errors: stats.errors,
+        p50_ms: millis(stats.latency.percentile(50.0)),
+        p90_ms: millis(stats.latency.percentile(90.0)),
+        p95_ms: millis(stats.latency.percentile(95.0)),
+        p99_ms: millis(stats.latency.percentile(99.0)),
+        max_ms: millis(stats.latency.max()),
+        timeline_bucket_ms: millis(stats.timeline.bucket()),
+        timeline: stats
+            .timeline
+            .buckets()
+            .iter()
+            .map(|timeline_bucket| JsonTimelineBucket {
+                offset_ms: millis(timeline_bucket.offset),
+                requests: timeline_bucket.requests,
+                errors: timeline_bucket.errors,
+            })
+            .collect(),
+    }
+}
+
+fn json_timeline_event(event: &TimelineEvent) -> JsonTimelineEvent {
+    match event {
+        TimelineEvent::ConcurrencyAdjusted {
+            elapsed,
+            from,
+            to,
+            error_rate,
+            latency,
+        } => JsonTimelineEvent::ConcurrencyAdjusted {
+            elapsed_ms: millis(*elapsed),
+            from: *from,
+            to: *to,
+            error_rate: *error_rate,
+            latency_ms: millis(*latency),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::json::*;
+    use serde_json::json;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn benchmark_result() -> BenchmarkResult {
+        let mut score = Score::new();
+        score.add_point_table("a", 3);
+        score.record("a");
+
+        let mut context = ErrorContext::request(surf::http::Method::Get, "/dummy");
+        context.status = Some(500);
+        context.scenario = Some("scenario1".into());
+        context.step = Some(0);
+        context.step_name = Some("step0".into());
+        context.timestamp = UNIX_EPOCH + Duration::from_millis(1500);
+        context.response_excerpt = Some("internal error".into());
+
+        let mut errors = Errors::new();
+        errors.record(ErrorRecord::new(
+            BenchmarkError::Penalty {
+                cause: "error_a".into(),
+                point: 1,
+            },
+            context,
+        ));
+
+        let mut step_result = BenchmarkStepResult::new(score, errors);
+        step_result.set_step_name("step0");
+        step_result.set_timing(Timing::new(
+            Duration::from_millis(20),
+            Duration::from_millis(50),
+        ));
+
+        let mut scenario_result = BenchmarkScenarioResult::new("scenario1");
+        scenario_result.set_phase(BenchmarkPhase::Load);
+        scenario_result.set_timing(Timing::new(
+            Duration::from_millis(10),
+            Duration::from_millis(60),
+        ));
+        scenario_result.add_step_result(step_result);
+
+        let mut canceled_result = BenchmarkScenarioResult::new("scenario2");
+        canceled_result.set_phase(BenchmarkPhase::Load);
+        canceled_result.set_status(ScenarioStatus::Canceled);
+
+        let mut benchmark_result = BenchmarkResult::new();
+        benchmark_result.add_scenario_result(scenario_result);
+        benchmark_result.add_unprocessed_result(canceled_result);
+        benchmark_result.set_timing(Timing::new(
+            Duration::from_millis(0),
+            Duration::from_millis(100),
+        ));
+        benchmark_result.add_phase_timing(PhaseTiming {
+            phase: BenchmarkPhase::Load,
+            timing: Timing::new(Duration::from_millis(5), Duration::from_millis(80)),
+        });
+        benchmark_result.add_timeline_event(TimelineEvent::ConcurrencyAdjusted {
+            elapsed: Duration::from_millis(1000),
+            from: 2,
+            to: 3,
+            error_rate: 0.0,
+            latency: Duration::from_millis(20),
+        });
+        benchmark_result.set_phase_requests(BenchmarkPhase::WarmUp, 3);
+
+        let mut endpoint_stats = EndpointStats::new("GET", "/dummy");
+        endpoint_stats.record(
+            Duration::from_millis(150),
+            Duration::from_millis(12),
+            Some(500),
+        );
+        benchmark_result.set_endpoint_stats(vec![endpoint_stats]);
+        benchmark_result
+    }
+
+    fn metadata() -> RunMetadata {
+        let mut metadata = RunMetadata::new();
+        metadata.set_base_url("http://localhost:8080");
+        metadata.set_parallels(4);
+        metadata.generated_at = UNIX_EPOCH + Duration::from_secs(2);
+        metadata
+    }
+
+    #[test]
+    fn test_json_report_schema() {
+        let report = JsonReport::new(&benchmark_result(), &metadata());
+
+        let error = json!({
+            "kind": "penalty",
+            "cause": "error_a",
+            "point": 1,
+            "code": "error_a",
+            "method": "GET",
+            "path": "/dummy",
+            "status": 500,
+            "scenario": "scenario1",
+            "step": 0,
+            "step_name": "step0",
+            "timestamp_ms": 1500,
+            "response_excerpt": "internal error"
+        });
+
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "schema_version": 2,
+                "metadata": {
+                    "tool": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "base_url": "http://localhost:8080",
+                    "parallels": 4,
+                    "generated_at_ms": 2000
+                },
+                "success": true,
+                "score": { "total": 2, "gain": 3, "lose": 1 },
+                "timing": { "started_at_ms": 0.0, "ended_at_ms": 100.0, "duration_ms": 100.0 },
+                "phases": [{
+                    "phase": "load",
+                    "scored": true,
+                    "skipped": false,
+                    "success": true,
+                    "score": { "total": 2, "gain": 3, "lose": 1 },
+                    "penalty_point": 0,
+                    "timing": { "started_at_ms": 5.0, "ended_at_ms": 80.0, "duration_ms": 75.0 }
+                }],
+                "breakdown": { "a": { "count": 1, "subtotal": 3 } },
+                "scenarios": [{
+                    "name": "scenario1",
+                    "phase": "load",
+                    "stage": null,
+                    "status": "processed",
+                    "success": true,
+                    "score": { "total": 2, "gain": 3, "lose": 1 },
+                    "timing": { "started_at_ms": 10.0, "ended_at_ms": 60.0, "duration_ms": 50.0 },
+                    "steps": [{
+                        "index": 0,
+                        "name": "step0",
+                        "success": true,
+                        "score": { "total": 2, "gain": 3, "lose": 1 },
+                        "timing": { "started_at_ms": 20.0, "ended_at_ms": 50.0, "duration_ms": 30.0 },
+                        "errors": [error]
+                    }]
+                }, {
+                    "name": "scenario2",
+                    "phase": "load",
+                    "stage": null,
+                    "status": "canceled",
+                    "success": true,
+                    "score": { "total": 0, "gain": 0, "lose": 0 },
+                    "timing": { "started_at_ms": 0.0, "ended_at_ms": 0.0, "duration_ms": 0.0 },
+                    "steps": []
+                }],
+                "scenario_counts": {
+                    "processed": 1,
+                    "canceled": 1,
+                    "timed_out": 0,
+                    "skipped": 0,
+                    "completion_ratio": 0.5
+                },
+                "error_groups": [{
+                    "category": "penalty",
+                    "code": "error_a",
+                    "count": 1,
+                    "penalty_point": 1,
+                    "example": error
+                }],
+                "budget_breaches": [],
+                "readiness_error": null,
+                "initialize_error": null,
+                "configuration_errors": [],
+                "timings": {
+                    "requests": 0,
+                    "latency_ms": 0.0,
+                    "rate_limit_wait_ms": 0.0
+                },
+                "warm_up_requests": 3,
+                "endpoint_stats": [{
+                    "method": "GET",
+                    "path": "/dummy",
+                    "requests": 1,
+                    "errors": 1,
+                    "p50_ms": 12.0,
+                    "p90_ms": 12.0,
+                    "p95_ms": 12.0,
+                    "p99_ms": 12.0,
+                    "max_ms": 12.0,
+                    "timeline_bucket_ms": 100.0,
+                    "timeline": [
+                        { "offset_ms": 0.0, "requests": 0, "errors": 0 },
+                        { "offset_ms": 100.0, "requests": 1, "errors": 1 }
+                    ]
+                }],
+                "dropped_starts": 0,
+                "timeline": [{
+                    "kind": "concurrency_adjusted",
+                    "elapsed_ms": 1000.0,
+                    "from": 2,
+                    "to": 3,
+                    "error_rate": 0.0,
+                    "latency_ms": 20.0
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_report_round_trip() {
+        let report = JsonReport::new(&benchmark_result(), &metadata());
+
+        let json = report.to_json().unwrap();
+        let deserialized: JsonReport = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, report);
+        assert_eq!(deserialized.schema_version, JSON_SCHEMA_VERSION);
+    }
+}
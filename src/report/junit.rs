@@ -0,0 +1,357 @@
+use crate::benchmark::scenario::*;
+use crate::benchmark::*;
+use crate::errors::*;
+use crate::report::*;
+
+use std::fmt::Write;
+
+pub fn junit_xml(result: &BenchmarkResult, metadata: &RunMetadata) -> String {
+    let details = result.details();
+    let unprocessed_details = result.unprocessed_details();
+    let budget_breaches = result.budget_breaches();
+    let startup_failures: Vec<_> = result
+        .readiness_error()
+        .map(|error| ("readiness", error.to_string()))
+        .into_iter()
+        .chain(
+            result
+                .initialize_error()
+                .map(|error| ("initialize", error.to_string())),
+        )
+        .collect();
+
+    let tests = details.len()
+        + unprocessed_details.len()
+        + usize::from(!budget_breaches.is_empty())
+        + startup_failures.len();
+    let failures = details.iter().filter(|detail| detail.is_failure()).count()
+        + usize::from(!budget_breaches.is_empty())
+        + startup_failures.len();
+    let errors = unprocessed_details
+        .iter()
+        .filter(|detail| detail.status() == ScenarioStatus::TimedOut)
+        .count();
+    let skipped = unprocessed_details.len() - errors;
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\">",
+        escape(&metadata.tool),
+        tests,
+        failures
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\" timestamp=\"{}\">",
+        escape(&metadata.tool),
+        tests,
+        failures,
+        errors,
+        skipped,
+        result.timing().duration().as_secs_f64(),
+        iso8601(metadata.generated_at)
+    );
+
+    xml.push_str("    <properties>\n");
+    let mut properties = vec![
+        ("score", result.total_score().to_string()),
+        ("gain", result.total_gain().to_string()),
+        ("lose", result.total_lose().to_string()),
+        ("success", result.is_success().to_string()),
+        ("version", metadata.version.clone()),
+    ];
+    if let Some(base_url) = &metadata.base_url {
+        properties.push(("base_url", base_url.clone()));
+    }
+    for (name, value) in properties {
+        let _ = writeln!(
+            xml,
+            "      <property name=\"{}\" value=\"{}\"/>",
+            name,
+            escape(&value)
+        );
+    }
+    xml.push_str("    </properties>\n");
+
+    for (name, message) in startup_failures {
+        let _ = writeln!(
+            xml,
+            "    <testcase name=\"{}\" classname=\"{}\">",
+            name,
+            escape(&metadata.tool)
+        );
+        let _ = writeln!(
+            xml,
+            "      <failure message=\"{}\" type=\"{}\"/>",
+            escape(&message),
+            name
+        );
+        xml.push_str("    </testcase>\n");
+    }
+
+    for detail in &details {
+        write_testcase(&mut xml, &metadata.tool, detail);
+    }
+
+    for detail in &unprocessed_details {
+        write_unprocessed_testcase(&mut xml, &metadata.tool, detail);
+    }
+
+    if !budget_breaches.is_empty() {
+        let _ = writeln!(
+            xml,
+            "    <testcase name=\"error_budget\" classname=\"{}\">",
+            escape(&metadata.tool)
+        );
+        for breach in &budget_breaches {
+            let _ = writeln!(
+                xml,
+                "      <failure message=\"{}\" type=\"budget\"/>",
+                escape(&breach.to_string())
+            );
+        }
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n");
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn write_testcase_start(xml: &mut String, tool: &str, result: &BenchmarkScenarioResult) {
+    let classname = match result.phase() {
+        Some(phase) => format!("{}.{}", tool, phase.name()),
+        None => tool.to_string(),
+    };
+    let _ = writeln!(
+        xml,
+        "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\">",
+        escape(&result.scenario_name()),
+        escape(&classname),
+        result.timing().duration().as_secs_f64()
+    );
+}
+
+fn write_unprocessed_testcase(xml: &mut String, tool: &str, result: &BenchmarkScenarioResult) {
+    write_testcase_start(xml, tool, result);
+
+    let status = status_name(result.status());
+    match result.status() {
+        ScenarioStatus::TimedOut => {
+            let _ = writeln!(
+                xml,
+                "      <error message=\"{}\" type=\"{}\"/>",
+                status, status
+            );
+        }
+        _ => {
+            let _ = writeln!(xml, "      <skipped message=\"{}\"/>", status);
+        }
+    }
+
+    xml.push_str("    </testcase>\n");
+}
+
+fn write_testcase(xml: &mut String, tool: &str, result: &BenchmarkScenarioResult) {
+    write_testcase_start(xml, tool, result);
+
+    let records = result.error_records();
+    let mut has_failure = false;
+    for record in records
+        .iter()
+        .filter(|record| matches!(record.error, BenchmarkError::Fail { .. }))
+    {
+        has_failure = true;
+        let _ = writeln!(
+            xml,
+            "      <failure message=\"{}\" type=\"{}\">{}</failure>",
+            escape(&record.code()),
+            record.category(),
+            escape(&record.to_string())
+        );
+    }
+    if result.is_failure() && !has_failure {
+        let messages: Vec<_> = result
+            .configuration_errors()
+            .iter()
+            .map(|error| error.to_string())
+            .collect();
+        let _ = writeln!(
+            xml,
+            "      <failure message=\"configuration error\" type=\"configuration\">{}</failure>",
+            escape(&messages.join("\n"))
+        );
+    }
+
+    let penalties: Vec<_> = records
+        .iter()
+        .filter(|record| matches!(record.error, BenchmarkError::Penalty { .. }))
+        .map(|record| record.to_string())
+        .collect();
+    let _ = writeln!(
+        xml,
+        "      <system-out>score: {} ({} - {}){}{}</system-out>",
+        result.total_score(),
+        result.total_gain(),
+        result.total_lose(),
+        if penalties.is_empty() { "" } else { "\n" },
+        escape(&penalties.join("\n"))
+    );
+
+    xml.push_str("    </testcase>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::benchmark::budget::*;
+    use crate::benchmark::initialize::*;
+    use crate::benchmark::step::*;
+    use crate::benchmark::timing::*;
+    use crate::report::junit::*;
+    use crate::score::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn scenario_result(name: &str, error: BenchmarkError) -> BenchmarkScenarioResult {
+        let mut score = Score::new();
+        score.add_point_table("a", 3);
+        score.record("a");
+
+        let mut context = ErrorContext::new();
+        context.timestamp = UNIX_EPOCH;
+
+        let mut errors = Errors::new();
+        errors.record(ErrorRecord::new(error, context));
+
+        let mut result = BenchmarkScenarioResult::new(name);
+        result.set_phase(BenchmarkPhase::Load);
+        result.set_timing(Timing::new(
+            Duration::from_millis(100),
+            Duration::from_millis(350),
+        ));
+        result.add_step_result(BenchmarkStepResult::new(score, errors));
+        result
+    }
+
+    fn metadata() -> RunMetadata {
+        let mut metadata = RunMetadata::new();
+        metadata.tool = "bench".into();
+        metadata.version = "1.0.0".into();
+        metadata.generated_at = UNIX_EPOCH + Duration::from_secs(2);
+        metadata
+    }
+
+    #[test]
+    fn test_junit_xml() {
+        let mut benchmark_result = BenchmarkResult::new();
+        benchmark_result.add_scenario_result(scenario_result(
+            "scenario1",
+            BenchmarkError::Penalty {
+                cause: "slow <response>".into(),
+                point: 1,
+            },
+        ));
+        benchmark_result.add_scenario_result(scenario_result(
+            "scenario2",
+            BenchmarkError::Fail {
+                cause: "broken".into(),
+            },
+        ));
+
+        assert_eq!(
+            junit_xml(&benchmark_result, &metadata()),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="bench" tests="2" failures="1">
+  <testsuite name="bench" tests="2" failures="1" errors="0" skipped="0" time="0.000" timestamp="1970-01-01T00:00:02">
+    <properties>
+      <property name="score" value="5"/>
+      <property name="gain" value="6"/>
+      <property name="lose" value="1"/>
+      <property name="success" value="false"/>
+      <property name="version" value="1.0.0"/>
+    </properties>
+    <testcase name="scenario1" classname="bench.load" time="0.250">
+      <system-out>score: 2 (3 - 1)
+benchmark penalty &quot;slow &lt;response&gt;&quot; : 1 (at 0.000)</system-out>
+    </testcase>
+    <testcase name="scenario2" classname="bench.load" time="0.250">
+      <failure message="broken" type="fail">benchmark fail &quot;broken&quot; (at 0.000)</failure>
+      <system-out>score: 3 (3 - 0)</system-out>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[test]
+    fn test_junit_xml_unprocessed() {
+        let mut benchmark_result = BenchmarkResult::new();
+        for (name, status) in [
+            ("scenario1", ScenarioStatus::Canceled),
+            ("scenario2", ScenarioStatus::TimedOut),
+            ("scenario3", ScenarioStatus::Skipped),
+        ]
+        .iter()
+        {
+            let mut result = BenchmarkScenarioResult::new(*name);
+            result.set_phase(BenchmarkPhase::Load);
+            result.set_status(*status);
+            benchmark_result.add_unprocessed_result(result);
+        }
+
+        let xml = junit_xml(&benchmark_result, &metadata());
+        assert!(xml.contains("<testsuites name=\"bench\" tests=\"3\" failures=\"0\">"));
+        assert!(xml.contains("tests=\"3\" failures=\"0\" errors=\"1\" skipped=\"2\""));
+        assert!(xml.contains(
+            r#"    <testcase name="scenario1" classname="bench.load" time="0.000">
+      <skipped message="canceled"/>
+    </testcase>
+    <testcase name="scenario2" classname="bench.load" time="0.000">
+      <error message="timed_out" type="timed_out"/>
+    </testcase>
+    <testcase name="scenario3" classname="bench.load" time="0.000">
+      <skipped message="skipped"/>
+    </testcase>
+"#
+        ));
+    }
+
+    #[test]
+    fn test_junit_xml_budget_breach() {
+        let mut benchmark_result = BenchmarkResult::new();
+        benchmark_result.add_scenario_result(scenario_result(
+            "scenario1",
+            BenchmarkError::Penalty {
+                cause: "slow".into(),
+                point: 5,
+            },
+        ));
+        benchmark_result.add_budget_breach(BudgetBreach::PenaltyPoint {
+            limit: 2,
+            actual: 5,
+        });
+
+        let xml = junit_xml(&benchmark_result, &metadata());
+        assert!(xml.contains("<testsuites name=\"bench\" tests=\"2\" failures=\"1\">"));
+        assert!(
+            xml.contains("<failure message=\"penalty point 5 exceeds budget 2\" type=\"budget\"/>")
+        );
+    }
+
+    #[test]
+    fn test_junit_xml_initialize_error() {
+        let mut benchmark_result = BenchmarkResult::new();
+        benchmark_result.set_initialize_error(InitializeError::Timeout {
+            path: "/initialize".into(),
+            deadline: Duration::from_secs(10),
+        });
+
+        let xml = junit_xml(&benchmark_result, &metadata());
+        assert!(xml.contains("<testsuites name=\"bench\" tests=\"1\" failures=\"1\">"));
+        assert!(xml.contains(
+            "<failure message=\"initialize /initialize did not respond within 10s\" type=\"initialize\"/>"
+        ));
+    }
+}
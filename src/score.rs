@@ -1,35 +1,203 @@
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use thiserror::Error;
 
 type PointName = String;
 type PointUnit = usize;
 
-#[derive(Clone)]
+#[derive(Clone, Error, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ScoreError {
+    #[error("unknown point {name:?} recorded {count:?} times")]
+    UnknownPoint { name: String, count: usize },
+}
+
+pub trait Point: Copy + 'static {
+    const ALL: &'static [Self];
+
+    fn name(&self) -> &'static str;
+    fn unit(&self) -> PointUnit;
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PointBreakdown {
+    pub count: usize,
+    pub subtotal: usize,
+}
+
+pub type ScoreBreakdown = BTreeMap<PointName, PointBreakdown>;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(from = "ScoreRecord", into = "ScoreRecord")]
 pub struct Score {
-    point_table: HashMap<PointName, PointUnit>,
-    records: Vec<PointName>,
+    point_table: Arc<HashMap<PointName, PointUnit>>,
+    records: HashMap<PointName, usize>,
+    unknown_records: HashMap<PointName, usize>,
 }
 
 impl Score {
     pub fn new() -> Score {
         Score {
-            point_table: HashMap::new(),
-            records: Vec::new(),
+            point_table: Arc::new(HashMap::new()),
+            records: HashMap::new(),
+            unknown_records: HashMap::new(),
         }
     }
 
+    pub fn from_points<P: Point>() -> Score {
+        let mut score = Score::new();
+        for point in P::ALL {
+            score.add_point_table(point.name(), point.unit());
+        }
+        score
+    }
+
     pub fn add_point_table(&mut self, point_name: impl Into<PointName>, point_unit: PointUnit) {
-        self.point_table.insert(point_name.into(), point_unit);
+        Arc::make_mut(&mut self.point_table).insert(point_name.into(), point_unit);
     }
 
-    pub fn record(&mut self, point_name: impl Into<PointName>) {
-        self.records.push(point_name.into());
+    /// Strict recording: an unknown point is rejected and returned to the caller.
+    /// `record` is lenient and reports it from `configuration_errors` instead.
+    pub fn try_record(&mut self, point_name: impl AsRef<str>) -> Result<(), ScoreError> {
+        let point_name = point_name.as_ref();
+        if !self.point_table.contains_key(point_name) {
+            return Err(ScoreError::UnknownPoint {
+                name: point_name.into(),
+                count: 1,
+            });
+        }
+
+        increment(&mut self.records, point_name);
+        Ok(())
     }
 
-    pub fn total(&self) -> usize {
+    pub fn record(&mut self, point_name: impl AsRef<str>) {
+        let point_name = point_name.as_ref();
+        if self.try_record(point_name).is_err() {
+            increment(&mut self.unknown_records, point_name);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.records.clear();
+        self.unknown_records.clear();
+    }
+
+    pub fn record_point<P: Point>(&mut self, point: P) {
+        self.record(point.name());
+    }
+
+    pub fn count(&self, point_name: impl AsRef<str>) -> usize {
+        self.records.get(point_name.as_ref()).copied().unwrap_or(0)
+    }
+
+    pub fn configuration_errors(&self) -> Vec<ScoreError> {
+        let mut errors: Vec<_> = self
+            .unknown_records
+            .iter()
+            .map(|(name, count)| ScoreError::UnknownPoint {
+                name: name.clone(),
+                count: *count,
+            })
+            .collect();
+        errors.sort_by_key(|error| error.to_string());
+        errors
+    }
+
+    pub fn breakdown(&self) -> ScoreBreakdown {
         self.records
             .iter()
-            .fold(0, |total, record| total + self.point_table[record])
+            .map(|(record, count)| {
+                let unit = self.point_table.get(record).copied().unwrap_or(0);
+                (
+                    record.clone(),
+                    PointBreakdown {
+                        count: *count,
+                        subtotal: unit * count,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    pub fn total(&self) -> usize {
+        self.records.iter().fold(0, |total, (record, count)| {
+            total + self.point_table.get(record).copied().unwrap_or(0) * count
+        })
+    }
+}
+
+impl PartialEq for Score {
+    fn eq(&self, other: &Self) -> bool {
+        self.breakdown() == other.breakdown() && self.unknown_records == other.unknown_records
+    }
+}
+
+// the shared point table is not serialized, only the units of recorded points
+#[derive(Serialize, Deserialize)]
+struct ScoreRecord {
+    records: ScoreBreakdown,
+    unknown_records: BTreeMap<PointName, usize>,
+}
+
+impl From<Score> for ScoreRecord {
+    fn from(score: Score) -> ScoreRecord {
+        ScoreRecord {
+            records: score.breakdown(),
+            unknown_records: score.unknown_records.into_iter().collect(),
+        }
+    }
+}
+
+impl From<ScoreRecord> for Score {
+    fn from(record: ScoreRecord) -> Score {
+        let mut score = Score::new();
+        for (name, point) in record.records {
+            score.add_point_table(name.clone(), point.subtotal / point.count.max(1));
+            score.records.insert(name, point.count);
+        }
+        score.unknown_records = record.unknown_records.into_iter().collect();
+        score
+    }
+}
+
+fn increment(records: &mut HashMap<PointName, usize>, point_name: &str) {
+    match records.get_mut(point_name) {
+        Some(count) => *count += 1,
+        None => {
+            records.insert(point_name.into(), 1);
+        }
+    }
+}
+
+pub(crate) fn merge_breakdowns(breakdowns: impl Iterator<Item = ScoreBreakdown>) -> ScoreBreakdown {
+    let mut merged = ScoreBreakdown::new();
+    for breakdown in breakdowns {
+        for (name, point) in breakdown {
+            let total = merged.entry(name).or_default();
+            total.count += point.count;
+            total.subtotal += point.subtotal;
+        }
     }
+    merged
+}
+
+pub(crate) fn merge_configuration_errors(
+    errors: impl Iterator<Item = ScoreError>,
+) -> Vec<ScoreError> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for ScoreError::UnknownPoint { name, count } in errors {
+        match counts.iter_mut().find(|(known, _)| *known == name) {
+            Some((_, total)) => *total += count,
+            None => counts.push((name, count)),
+        }
+    }
+    counts.sort();
+
+    counts
+        .into_iter()
+        .map(|(name, count)| ScoreError::UnknownPoint { name, count })
+        .collect()
 }
 
 #[cfg(test)]
@@ -51,13 +219,19 @@ mod tests {
     #[test]
     fn test_record() {
         let mut score = Score::new();
+        score.add_point_table("a", 1);
+        score.add_point_table("b", 2);
+        score.add_point_table("c", 3);
         score.record("a");
         score.record("b");
-        score.record("c");
+        score.record("b");
+        score.record(String::from("c"));
 
-        assert_eq!(score.records[0], "a");
-        assert_eq!(score.records[1], "b");
-        assert_eq!(score.records[2], "c");
+        assert_eq!(score.records["a"], 1);
+        assert_eq!(score.records["b"], 2);
+        assert_eq!(score.records["c"], 1);
+        assert_eq!(score.count("b"), 2);
+        assert_eq!(score.count("d"), 0);
     }
 
     #[test]
@@ -72,4 +246,234 @@ mod tests {
 
         assert_eq!(score.total(), 6);
     }
+
+    #[test]
+    fn test_total_repeated() {
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+        score.add_point_table("b", 2);
+        for _ in 0..1000 {
+            score.record("a");
+            score.record("b");
+        }
+
+        assert_eq!(score.total(), 3000);
+        assert_eq!(score.records.len(), 2);
+    }
+
+    #[test]
+    fn test_point_table_shared() {
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+
+        let mut cloned = score.clone();
+        assert!(Arc::ptr_eq(&score.point_table, &cloned.point_table));
+
+        cloned.record("a");
+        assert_eq!(score.total(), 0);
+        assert_eq!(cloned.total(), 1);
+
+        cloned.add_point_table("b", 2);
+        assert!(!Arc::ptr_eq(&score.point_table, &cloned.point_table));
+        assert!(!score.point_table.contains_key("b"));
+    }
+
+    #[test]
+    fn test_serde_without_point_table() {
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+        score.add_point_table("b", 2);
+        score.add_point_table("unused", 5);
+        score.record("b");
+        score.record("b");
+        score.record("typo");
+
+        let json = serde_json::to_string(&score).unwrap();
+        assert!(!json.contains("unused"));
+
+        let deserialized: Score = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, score);
+        assert_eq!(deserialized.total(), 4);
+        assert_eq!(deserialized.count("b"), 2);
+        assert_eq!(
+            deserialized.configuration_errors(),
+            score.configuration_errors()
+        );
+    }
+
+    #[test]
+    fn test_record_unknown_point() {
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+        score.record("a");
+        score.record("typo");
+        score.record("typo");
+
+        assert_eq!(score.total(), 1);
+        assert_eq!(score.count("typo"), 0);
+        assert_eq!(
+            score.configuration_errors(),
+            vec![ScoreError::UnknownPoint {
+                name: "typo".into(),
+                count: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_try_record() {
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+
+        assert_eq!(score.try_record("a"), Ok(()));
+        assert_eq!(
+            score.try_record("typo"),
+            Err(ScoreError::UnknownPoint {
+                name: "typo".into(),
+                count: 1
+            })
+        );
+        assert_eq!(score.total(), 1);
+        assert!(score.configuration_errors().is_empty());
+    }
+
+    #[derive(Clone, Copy)]
+    enum TestPoint {
+        Login,
+        PostComment,
+    }
+
+    impl Point for TestPoint {
+        const ALL: &'static [TestPoint] = &[TestPoint::Login, TestPoint::PostComment];
+
+        fn name(&self) -> &'static str {
+            match self {
+                TestPoint::Login => "login",
+                TestPoint::PostComment => "post_comment",
+            }
+        }
+
+        fn unit(&self) -> PointUnit {
+            match self {
+                TestPoint::Login => 1,
+                TestPoint::PostComment => 3,
+            }
+        }
+    }
+
+    #[test]
+    fn test_record_point() {
+        let mut score = Score::from_points::<TestPoint>();
+        score.record_point(TestPoint::Login);
+        score.record_point(TestPoint::PostComment);
+        score.record_point(TestPoint::PostComment);
+
+        assert_eq!(score.count("login"), 1);
+        assert_eq!(score.count("post_comment"), 2);
+        assert_eq!(score.total(), 7);
+        assert!(score.configuration_errors().is_empty());
+    }
+
+    #[test]
+    fn test_merge_configuration_errors() {
+        let errors = vec![
+            ScoreError::UnknownPoint {
+                name: "b".into(),
+                count: 1,
+            },
+            ScoreError::UnknownPoint {
+                name: "a".into(),
+                count: 2,
+            },
+            ScoreError::UnknownPoint {
+                name: "b".into(),
+                count: 3,
+            },
+        ];
+
+        assert_eq!(
+            merge_configuration_errors(errors.into_iter()),
+            vec![
+                ScoreError::UnknownPoint {
+                    name: "a".into(),
+                    count: 2
+                },
+                ScoreError::UnknownPoint {
+                    name: "b".into(),
+                    count: 4
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_breakdown() {
+        let mut score = Score::new();
+        score.add_point_table("login", 1);
+        score.add_point_table("post_comment", 3);
+        score.record("login");
+        score.record("post_comment");
+        score.record("post_comment");
+
+        let breakdown = score.breakdown();
+        assert_eq!(breakdown.len(), 2);
+        assert_eq!(
+            breakdown["login"],
+            PointBreakdown {
+                count: 1,
+                subtotal: 1
+            }
+        );
+        assert_eq!(
+            breakdown["post_comment"],
+            PointBreakdown {
+                count: 2,
+                subtotal: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_merge_breakdowns() {
+        let mut score1 = Score::new();
+        score1.add_point_table("a", 1);
+        score1.add_point_table("b", 2);
+        let mut score2 = score1.clone();
+
+        score1.record("a");
+        score1.record("b");
+        score2.record("b");
+
+        let merged = merge_breakdowns(vec![score1.breakdown(), score2.breakdown()].into_iter());
+        assert_eq!(
+            merged["a"],
+            PointBreakdown {
+                count: 1,
+                subtotal: 1
+            }
+        );
+        assert_eq!(
+            merged["b"],
+            PointBreakdown {
+                count: 2,
+                subtotal: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_score_serde() {
+        let mut score = Score::new();
+        score.add_point_table("a", 1);
+        score.add_point_table("b", 2);
+        score.record("a");
+        score.record("typo");
+
+        let json = serde_json::to_string(&score).unwrap();
+        let deserialized: Score = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, score);
+        assert_eq!(deserialized.total(), 1);
+        assert_eq!(deserialized.configuration_errors().len(), 1);
+    }
 }
@@ -0,0 +1,72 @@
+use async_std::future;
+use async_std::task;
+use bench_rs::agent::*;
+use bench_rs::benchmark::scenario::*;
+use bench_rs::benchmark::step::*;
+use bench_rs::benchmark::*;
+use bench_rs::errors::*;
+use bench_rs::score::*;
+use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+const SCENARIOS: usize = 5000;
+const PARALLELS: usize = 2000;
+
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+static MAX_IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+fn step(agent: Agent, mut score: Score, errors: Errors) -> BoxFutBenchmarkStep {
+    Box::pin(async move {
+        let in_flight = IN_FLIGHT.fetch_add(1, Ordering::SeqCst) + 1;
+        MAX_IN_FLIGHT.fetch_max(in_flight, Ordering::SeqCst);
+
+        task::sleep(Duration::from_millis(200)).await;
+
+        let response = agent.get("/stress").await;
+        assert_eq!(response.unwrap().status(), surf::StatusCode::Ok);
+
+        score.record("a");
+
+        IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+
+        BenchmarkStepResult::new(score, errors)
+    })
+}
+
+// runs in its own test binary because the executor reads the thread count only once
+#[test]
+fn test_load_scenario_stress() {
+    env::set_var("ASYNC_STD_THREAD_COUNT", "2");
+
+    let _m = mockito::mock("GET", "/stress")
+        .with_status(surf::StatusCode::Ok as usize)
+        .expect(SCENARIOS)
+        .create();
+
+    let base_url = &mockito::server_url();
+
+    let agent = Agent::new(base_url);
+
+    let mut score = Score::new();
+    score.add_point_table("a", 1);
+
+    let errors = Errors::new();
+
+    let mut benchmark = Benchmark::new(agent, score, errors, PARALLELS);
+    for i in 0..SCENARIOS {
+        let mut benchmark_scenario = BenchmarkScenario::new(format!("scenario{}", i));
+        benchmark_scenario.add_benchmark_step(step);
+        benchmark.add_load_scenario(benchmark_scenario);
+    }
+
+    let benchmark_result =
+        task::block_on(future::timeout(Duration::from_secs(30), benchmark.start()))
+            .expect("load pipeline stalled");
+
+    assert_eq!(MAX_IN_FLIGHT.load(Ordering::SeqCst), PARALLELS);
+    assert_eq!(benchmark_result.details().len(), SCENARIOS);
+    assert_eq!(benchmark_result.total_gain(), SCENARIOS as isize);
+    assert_eq!(benchmark_result.agent_metrics().requests, SCENARIOS);
+    _m.assert();
+}